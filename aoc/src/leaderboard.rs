@@ -0,0 +1,265 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    fs::File,
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+};
+
+use serde::{de, Deserialize, Deserializer};
+
+pub fn run(args: &[String]) {
+    let path = match args.first() {
+        Some(path) => PathBuf::from(path),
+        None => default_cache_path(),
+    };
+
+    let leaderboard = Leaderboard::load(&path).unwrap_or_else(|e| {
+        eprintln!(
+            "error: failed to read leaderboard {}: {}",
+            path.display(),
+            e
+        );
+        eprintln!("download it from your private leaderboard's [API] link, or pass a path");
+        std::process::exit(1);
+    });
+
+    let implemented = crate::implemented_days(&crate::repo_root());
+    print!("{}", report(&leaderboard, &implemented));
+}
+
+// Where a downloaded copy of the leaderboard JSON is expected to live.
+fn default_cache_path() -> PathBuf {
+    crate::repo_root().join(".cache").join("leaderboard.json")
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Leaderboard {
+    /// The year, which the API sends as a string.
+    #[serde(deserialize_with = "year")]
+    pub event: i64,
+    pub members: HashMap<String, Member>,
+}
+
+fn year<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    let event = String::deserialize(deserializer)?;
+    event
+        .parse()
+        .map_err(|_| de::Error::custom(format!("event `{}` isn't a year", event)))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    pub last_star_ts: u64,
+    pub completion_day_level: BTreeMap<u32, BTreeMap<u32, Star>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Star {
+    pub get_star_ts: u64,
+}
+
+impl Leaderboard {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    /// Members ordered by local score, ties going to whoever got their last star first.
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members: Vec<_> = self.members.values().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+        members
+    }
+
+    /// Unix timestamp at which the given day's puzzle unlocked (midnight EST).
+    pub fn unlock_ts(&self, day: u32) -> u64 {
+        let days = days_from_civil(self.event, 12, day as i64);
+        (days * 86_400 + 5 * 3_600) as u64
+    }
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    pub fn star_ts(&self, day: u32, part: u32) -> Option<u64> {
+        self.completion_day_level
+            .get(&day)
+            .and_then(|parts| parts.get(&part))
+            .map(|star| star.get_star_ts)
+    }
+
+    /// Seconds between getting the first and second star of a day.
+    pub fn delta(&self, day: u32) -> Option<u64> {
+        let first = self.star_ts(day, 1)?;
+        let second = self.star_ts(day, 2)?;
+        Some(second.saturating_sub(first))
+    }
+}
+
+pub fn report(leaderboard: &Leaderboard, implemented: &[u32]) -> String {
+    let ranking = leaderboard.ranking();
+    let name_width = ranking
+        .iter()
+        .map(|m| m.display_name().len())
+        .max()
+        .unwrap_or(0);
+    let mut days: Vec<u32> = ranking
+        .iter()
+        .flat_map(|m| m.completion_day_level.keys().copied())
+        .collect();
+    days.sort();
+    days.dedup();
+    let day_label = |day: u32| {
+        let marker = if implemented.contains(&day) { "*" } else { " " };
+        format!("Day {:>2}{}", day, marker)
+    };
+
+    let mut out = String::new();
+    writeln!(out, "Local score ranking ({})", leaderboard.event).unwrap();
+    for (i, member) in ranking.iter().enumerate() {
+        writeln!(
+            out,
+            "{:>3}) {:<name_width$} {:>5} pts {:>3} stars",
+            i + 1,
+            member.display_name(),
+            member.local_score,
+            member.stars,
+        )
+        .unwrap();
+    }
+
+    writeln!(out, "\nStar times after unlock (* = solved in this repo)").unwrap();
+    for member in &ranking {
+        writeln!(out, "{}", member.display_name()).unwrap();
+        for &day in member.completion_day_level.keys() {
+            let unlock = leaderboard.unlock_ts(day);
+            let part = |part| match member.star_ts(day, part) {
+                Some(ts) => format_duration(ts.saturating_sub(unlock)),
+                None => "-".to_owned(),
+            };
+            writeln!(
+                out,
+                "  {}  part 1 {:>10}  part 2 {:>10}",
+                day_label(day),
+                part(1),
+                part(2)
+            )
+            .unwrap();
+        }
+    }
+
+    writeln!(out, "\nPart 1 to part 2 deltas").unwrap();
+    for day in days {
+        let mut deltas: Vec<_> = ranking
+            .iter()
+            .filter_map(|m| Some((m.delta(day)?, m.display_name())))
+            .collect();
+        if deltas.is_empty() {
+            continue;
+        }
+        deltas.sort();
+        writeln!(out, "{}", day_label(day)).unwrap();
+        for (delta, name) in deltas {
+            writeln!(
+                out,
+                "  {:<name_width$} {:>10}",
+                name,
+                format_duration(delta)
+            )
+            .unwrap();
+        }
+    }
+
+    out
+}
+
+pub fn format_duration(seconds: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3_600,
+        (seconds / 60) % 60,
+        seconds % 60
+    )
+}
+
+// Days since the unix epoch for a proleptic Gregorian date (Howard Hinnant's algorithm).
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Leaderboard {
+        Leaderboard::load(Path::new("test_leaderboard.json")).unwrap()
+    }
+
+    #[test]
+    fn test_unlock_ts() {
+        let leaderboard = sample();
+        assert_eq!(1669870800, leaderboard.unlock_ts(1));
+        assert_eq!(1669957200, leaderboard.unlock_ts(2));
+    }
+
+    #[test]
+    fn test_bad_event() {
+        let json = r#"{"event": "advent", "members": {}}"#;
+        let err = serde_json::from_str::<Leaderboard>(json).unwrap_err();
+        assert!(err.to_string().starts_with("event `advent` isn't a year"));
+    }
+
+    #[test]
+    fn test_ranking() {
+        let leaderboard = sample();
+        let names: Vec<_> = leaderboard
+            .ranking()
+            .iter()
+            .map(|m| m.display_name())
+            .collect();
+        assert_eq!(vec!["Alice", "(anonymous user #1002)", "Bob"], names);
+    }
+
+    #[test]
+    fn test_delta() {
+        let leaderboard = sample();
+        let alice = &leaderboard.members["1001"];
+        assert_eq!(Some(300), alice.delta(1));
+        assert_eq!(Some(3600), alice.delta(2));
+        assert_eq!(None, leaderboard.members["1003"].delta(1));
+        assert_eq!("01:00:00", format_duration(3600));
+    }
+
+    #[test]
+    fn test_report() {
+        let report = report(&sample(), &[1]);
+        assert!(report.contains("  1) Alice                     11 pts   4 stars"));
+        assert!(report.contains("  Day  1*  part 1   00:10:00  part 2   00:15:00"));
+        assert!(report.contains("  Day  2   part 1   00:20:00  part 2   01:20:00"));
+        assert!(report.contains("  Day  1*  part 1   00:30:00  part 2          -"));
+        assert!(report.contains(
+            "Day  1*\n  Alice                    00:05:00\n  (anonymous user #1002)   01:00:00\n"
+        ));
+    }
+}
@@ -0,0 +1,51 @@
+use std::path::{Path, PathBuf};
+
+mod leaderboard;
+mod new_day;
+mod run_all;
+
+const USAGE: &str = "\
+Usage: aoc <command> [args]
+
+Commands:
+    leaderboard [path]    Report on a private leaderboard JSON file
+                          (defaults to .cache/leaderboard.json)
+    new <day>             Generate a new day crate from the template
+    run [--jobs N] [--sort day|time]
+                          Build and run every day in parallel and summarize";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("leaderboard") => leaderboard::run(&args[1..]),
+        Some("new") => new_day::run(&args[1..]),
+        Some("run") => run_all::run(&args[1..]),
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_owned()
+}
+
+/// Day numbers of the `dayNN` crates in the repo.
+pub fn implemented_days(root: &Path) -> Vec<u32> {
+    let mut days: Vec<u32> = std::fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("Cargo.toml").exists())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            name.strip_prefix("day")?.parse().ok()
+        })
+        .collect();
+    days.sort();
+    days
+}
@@ -0,0 +1,137 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+const CARGO_TOML: &str = include_str!("../template/Cargo.toml.tmpl");
+const MAIN_RS: &str = include_str!("../template/main.rs.tmpl");
+
+pub fn run(args: &[String]) {
+    let day = match args.first().and_then(|d| d.parse::<u32>().ok()) {
+        Some(day @ 1..=25) => day,
+        _ => {
+            eprintln!("error: expected a day between 1 and 25");
+            std::process::exit(2);
+        }
+    };
+
+    match generate(&crate::repo_root(), day) {
+        Ok(dir) => println!("Created {}", dir.display()),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+pub fn crate_name(day: u32) -> String {
+    format!("day{:02}", day)
+}
+
+/// Creates a new day crate under `root`, refusing to touch a day that already exists. The crate
+/// is written into a temporary sibling directory and renamed into place once it's complete, so
+/// a failed write never leaves half a crate behind.
+pub fn generate(root: &Path, day: u32) -> io::Result<PathBuf> {
+    let name = crate_name(day);
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("{} already exists", dir.display()),
+        ));
+    }
+
+    // Left over if an earlier run was killed part way through.
+    let partial = root.join(format!(".{}.partial", name));
+    if partial.exists() {
+        fs::remove_dir_all(&partial)?;
+    }
+    if let Err(e) = write_files(&partial, day).and_then(|()| fs::rename(&partial, &dir)) {
+        let _ = fs::remove_dir_all(&partial);
+        return Err(e);
+    }
+
+    Ok(dir)
+}
+
+fn write_files(dir: &Path, day: u32) -> io::Result<()> {
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(dir.join("Cargo.toml"), render(CARGO_TOML, day))?;
+    fs::write(dir.join("src").join("main.rs"), render(MAIN_RS, day))?;
+    fs::write(dir.join("test_input.txt"), "")?;
+    fs::write(dir.join("input.txt"), "")
+}
+
+fn render(template: &str, day: u32) -> String {
+    template
+        .replace("{{name}}", &crate_name(day))
+        .replace("{{day}}", &day.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc_new_day_{}_{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_crate_name() {
+        assert_eq!("day03", crate_name(3));
+        assert_eq!("day25", crate_name(25));
+    }
+
+    #[test]
+    fn test_generate() {
+        let root = scratch_dir("generate");
+        let dir = generate(&root, 13).unwrap();
+
+        assert_eq!(root.join("day13"), dir);
+        let cargo_toml = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"day13\""));
+        assert!(cargo_toml.contains("path = \"../helpers\""));
+        let main_rs = fs::read_to_string(dir.join("src/main.rs")).unwrap();
+        assert!(main_rs.contains("Results::new(13, &args)"));
+        assert!(main_rs.contains("fn part1_sample()"));
+        assert!(main_rs.contains("fn part2_final()"));
+        assert_eq!("", fs::read_to_string(dir.join("test_input.txt")).unwrap());
+        assert_eq!(1, fs::read_dir(&root).unwrap().count());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_generate_after_interrupted_run() {
+        let root = scratch_dir("interrupted");
+        fs::create_dir_all(root.join(".day14.partial").join("src")).unwrap();
+        fs::write(root.join(".day14.partial").join("Cargo.toml"), "[pack").unwrap();
+
+        let dir = generate(&root, 14).unwrap();
+        let cargo_toml = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"day14\""));
+        assert!(!root.join(".day14.partial").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_generate_refuses_existing_day() {
+        let root = scratch_dir("existing");
+        fs::create_dir_all(root.join("day01")).unwrap();
+        fs::write(root.join("day01").join("input.txt"), "1000").unwrap();
+
+        let err = generate(&root, 1).unwrap_err();
+        assert_eq!(ErrorKind::AlreadyExists, err.kind());
+        assert_eq!(
+            "1000",
+            fs::read_to_string(root.join("day01").join("input.txt")).unwrap()
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
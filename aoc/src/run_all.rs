@@ -0,0 +1,333 @@
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use helpers::PartResult;
+
+use crate::new_day::crate_name;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortBy {
+    Day,
+    Time,
+}
+
+#[derive(Debug)]
+pub struct DayOutcome {
+    pub day: u32,
+    pub elapsed: Duration,
+    pub result: Result<Vec<PartResult>, String>,
+}
+
+pub fn run(args: &[String]) {
+    let (jobs, sort_by) = parse_args(args).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(2);
+    });
+
+    let root = crate::repo_root();
+    let days = crate::implemented_days(&root);
+
+    let now = Instant::now();
+    let builds = run_pool(&days, jobs, |day| (day, build_day(&root, day)));
+    eprintln!("Built {} days in {:.2?}", days.len(), now.elapsed());
+
+    let (built, failed): (Vec<_>, Vec<_>) = builds.into_iter().partition(|(_, b)| b.is_ok());
+    let built: Vec<u32> = built.into_iter().map(|(day, _)| day).collect();
+
+    let now = Instant::now();
+    let mut outcomes = run_pool(&built, jobs, |day| run_day(&root, day));
+    let wall_clock = now.elapsed();
+
+    outcomes.extend(failed.into_iter().map(|(day, build)| DayOutcome {
+        day,
+        elapsed: Duration::ZERO,
+        result: Err(build.unwrap_err()),
+    }));
+
+    match sort_by {
+        SortBy::Day => outcomes.sort_by_key(|o| o.day),
+        SortBy::Time => outcomes.sort_by_key(|o| std::cmp::Reverse(o.elapsed)),
+    }
+    print!("{}", summary(&outcomes, wall_clock));
+
+    if outcomes.iter().any(|o| o.result.is_err()) {
+        std::process::exit(1);
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<(usize, SortBy), String> {
+    let mut jobs = thread::available_parallelism().map_or(1, |n| n.get());
+    let mut sort_by = SortBy::Day;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for `{}`", arg));
+        match arg.as_str() {
+            "--jobs" => {
+                jobs = value()?
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or("`--jobs` must be a positive number")?;
+            }
+            "--sort" => {
+                sort_by = match value()?.as_str() {
+                    "day" => SortBy::Day,
+                    "time" => SortBy::Time,
+                    other => return Err(format!("unknown sort `{}`", other)),
+                };
+            }
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+
+    Ok((jobs, sort_by))
+}
+
+/// Runs `job` for every day on `threads` worker threads, returning results in completion order.
+fn run_pool<T, F>(days: &[u32], threads: usize, job: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(u32) -> T + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(days.len()));
+
+    thread::scope(|s| {
+        for _ in 0..threads.min(days.len()) {
+            s.spawn(|| {
+                while let Some(&day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let result = job(day);
+                    results.lock().unwrap().push(result);
+                }
+            });
+        }
+    });
+
+    results.into_inner().unwrap()
+}
+
+// Days are built before any of them run so that compiling doesn't skew the timings.
+fn build_day(root: &Path, day: u32) -> Result<(), String> {
+    let manifest = root.join(crate_name(day)).join("Cargo.toml");
+    let output = Command::new(env!("CARGO"))
+        .args(["build", "--release", "--quiet", "--manifest-path"])
+        .arg(manifest)
+        .output()
+        .map_err(|e| format!("failed to run cargo: {}", e))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr
+            .lines()
+            .find(|l| l.starts_with("error"))
+            .unwrap_or("");
+        Err(format!("build failed: {}", message))
+    }
+}
+
+fn run_day(root: &Path, day: u32) -> DayOutcome {
+    let name = crate_name(day);
+    let dir = root.join(&name);
+    let failed = |elapsed, message| DayOutcome {
+        day,
+        elapsed,
+        result: Err(message),
+    };
+
+    let now = Instant::now();
+    let output = Command::new(binary_path(&dir, &name))
+        .args(["--format", "json"])
+        .output();
+    let elapsed = now.elapsed();
+
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => return failed(elapsed, format!("failed to start: {}", e)),
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = panic_message(&stderr).unwrap_or_else(|| output.status.to_string());
+        return failed(elapsed, message);
+    }
+
+    match serde_json::from_slice(&output.stdout) {
+        Ok(parts) => DayOutcome {
+            day,
+            elapsed,
+            result: Ok(parts),
+        },
+        Err(e) => failed(elapsed, format!("unreadable output: {}", e)),
+    }
+}
+
+fn binary_path(dir: &Path, name: &str) -> PathBuf {
+    let target_dir = match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(target_dir) => PathBuf::from(target_dir),
+        None => dir.join("target"),
+    };
+    target_dir.join("release").join(name)
+}
+
+/// Pulls the panic location and message out of a day's stderr.
+fn panic_message(stderr: &str) -> Option<String> {
+    let mut lines = stderr.lines().skip_while(|l| !l.contains("panicked at"));
+    let location = lines.next()?.trim();
+    let location = location
+        .split_once("panicked at ")
+        .map_or(location, |(_, l)| l);
+    match lines.next() {
+        Some(message) if !message.starts_with("note:") => {
+            Some(format!("panicked at {} {}", location, message.trim()))
+        }
+        _ => Some(format!("panicked at {}", location)),
+    }
+}
+
+fn summary(outcomes: &[DayOutcome], wall_clock: Duration) -> String {
+    let total: Duration = outcomes.iter().map(|o| o.elapsed).sum();
+    let failures = outcomes.iter().filter(|o| o.result.is_err()).count();
+
+    let mut out = String::new();
+    for outcome in outcomes {
+        let share = if total.is_zero() {
+            0.0
+        } else {
+            100.0 * outcome.elapsed.as_secs_f64() / total.as_secs_f64()
+        };
+        write!(
+            out,
+            "{}  {:>12}  {:>5.1}%  ",
+            crate_name(outcome.day),
+            format!("{:.2?}", outcome.elapsed),
+            share
+        )
+        .unwrap();
+
+        match &outcome.result {
+            Ok(parts) => {
+                let mut multi_line = Vec::new();
+                let answers: Vec<_> = parts
+                    .iter()
+                    .map(|p| {
+                        if p.answer.contains('\n') {
+                            multi_line.push(&p.answer);
+                            format!("Part {}: (see below)", p.part)
+                        } else {
+                            format!("Part {}: {}", p.part, p.answer)
+                        }
+                    })
+                    .collect();
+                writeln!(out, "{}", answers.join("  ")).unwrap();
+                for answer in multi_line {
+                    for line in answer.lines() {
+                        writeln!(out, "    {}", line).unwrap();
+                    }
+                }
+            }
+            Err(message) => writeln!(out, "FAILED: {}", message).unwrap(),
+        }
+    }
+
+    writeln!(
+        out,
+        "\n{} days, {} failed, {:.2?} wall clock ({:.2?} across days)",
+        outcomes.len(),
+        failures,
+        wall_clock,
+        total
+    )
+    .unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(day: u32, part: u32, answer: &str) -> PartResult {
+        PartResult {
+            day,
+            part,
+            answer: answer.to_owned(),
+            input_hash: "0".to_owned(),
+            elapsed_ns: 0,
+        }
+    }
+
+    #[test]
+    fn test_panic_message() {
+        let stderr = "\nthread 'main' panicked at src/main.rs:55:18:\ncalled `Option::unwrap()` on a `None` value\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n";
+        assert_eq!(
+            Some(
+                "panicked at src/main.rs:55:18: called `Option::unwrap()` on a `None` value"
+                    .to_owned()
+            ),
+            panic_message(stderr)
+        );
+        assert_eq!(None, panic_message("error: input file `x` not found\n"));
+    }
+
+    #[test]
+    fn test_run_pool() {
+        let days: Vec<u32> = (1..=12).collect();
+        let mut outcomes = run_pool(&days, 4, |day| DayOutcome {
+            day,
+            elapsed: Duration::from_millis(day as u64),
+            result: if day == 7 {
+                Err("boom".to_owned())
+            } else {
+                Ok(vec![part(day, 1, "1")])
+            },
+        });
+        outcomes.sort_by_key(|o| o.day);
+
+        assert_eq!(days, outcomes.iter().map(|o| o.day).collect::<Vec<_>>());
+        assert_eq!(Err("boom".to_owned()), outcomes[6].result);
+    }
+
+    #[test]
+    fn test_summary() {
+        let outcomes = vec![
+            DayOutcome {
+                day: 1,
+                elapsed: Duration::from_millis(30),
+                result: Ok(vec![part(1, 1, "67016"), part(1, 2, "200116")]),
+            },
+            DayOutcome {
+                day: 10,
+                elapsed: Duration::from_millis(10),
+                result: Ok(vec![part(10, 1, "13140"), part(10, 2, "##..\n.##.")]),
+            },
+            DayOutcome {
+                day: 11,
+                elapsed: Duration::from_millis(60),
+                result: Err("panicked at src/main.rs:1:1".to_owned()),
+            },
+        ];
+
+        assert_eq!(
+            "\
+day01       30.00ms   30.0%  Part 1: 67016  Part 2: 200116
+day10       10.00ms   10.0%  Part 1: 13140  Part 2: (see below)
+    ##..
+    .##.
+day11       60.00ms   60.0%  FAILED: panicked at src/main.rs:1:1
+
+3 days, 1 failed, 50.00ms wall clock (100.00ms across days)
+",
+            summary(&outcomes, Duration::from_millis(50))
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fmt::{self, Display, Write},
+    num::ParseIntError,
+};
+
+/// Running statistics for one elf's inventory. `index` is 1-based, in input order.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub struct Elf {
+    pub index: usize,
+    pub items: usize,
+    pub total: usize,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Elf {
+    fn new(index: usize) -> Self {
+        Self {
+            index,
+            items: 0,
+            total: 0,
+            min: usize::MAX,
+            max: 0,
+        }
+    }
+
+    fn add(&mut self, calories: usize) {
+        self.items += 1;
+        self.total += calories;
+        self.min = self.min.min(calories);
+        self.max = self.max.max(calories);
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.total as f64 / self.items as f64
+    }
+}
+
+/// An inventory line that isn't a calorie count. `line` and `elf` are 1-based.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct BadLine {
+    pub line: usize,
+    pub elf: usize,
+    pub text: String,
+    pub error: ParseIntError,
+}
+
+impl Display for BadLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {} (elf {}): `{}` is not a calorie count: {}",
+            self.line, self.elf, self.text, self.error
+        )
+    }
+}
+
+/// Streams elves out of inventory lines without holding more than one elf in memory.
+/// Surrounding whitespace (including a Windows `\r`) is ignored, and lines that aren't
+/// calorie counts are skipped and recorded in `bad_lines` rather than panicking.
+pub struct Elves<I> {
+    lines: I,
+    line_number: usize,
+    next_index: usize,
+    bad_lines: Vec<BadLine>,
+}
+
+pub fn elves<I>(lines: I) -> Elves<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    Elves {
+        lines: lines.into_iter(),
+        line_number: 0,
+        next_index: 1,
+        bad_lines: Vec::new(),
+    }
+}
+
+impl<I> Elves<I> {
+    /// Bad lines seen so far; complete once the iterator is exhausted.
+    pub fn into_bad_lines(self) -> Vec<BadLine> {
+        self.bad_lines
+    }
+}
+
+impl<I> Iterator for Elves<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = Elf;
+
+    fn next(&mut self) -> Option<Elf> {
+        loop {
+            let mut elf = Elf::new(self.next_index);
+            let mut in_group = false;
+            for line in self.lines.by_ref() {
+                self.line_number += 1;
+                let line = line.as_ref().trim();
+                if line.is_empty() {
+                    if in_group {
+                        break;
+                    }
+                    continue;
+                }
+
+                in_group = true;
+                match line.parse() {
+                    Ok(calories) => elf.add(calories),
+                    Err(error) => self.bad_lines.push(BadLine {
+                        line: self.line_number,
+                        elf: self.next_index,
+                        text: line.to_owned(),
+                        error,
+                    }),
+                }
+            }
+
+            if !in_group {
+                return None;
+            }
+            // An elf whose every line was bad still takes up an index.
+            self.next_index += 1;
+            if elf.items > 0 {
+                return Some(elf);
+            }
+        }
+    }
+}
+
+/// The `k` largest items, largest first, keeping only `k` of them in memory at a time.
+pub fn top_k<T: Ord>(items: impl Iterator<Item = T>, k: usize) -> Vec<T> {
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for item in items {
+        heap.push(Reverse(item));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(item)| item)
+        .collect()
+}
+
+/// The `k` elves carrying the most calories, ties going to the earlier elf.
+pub fn top_elves(elves: impl Iterator<Item = Elf>, k: usize) -> Vec<Elf> {
+    top_k(elves.map(|elf| (elf.total, Reverse(elf.index), elf)), k)
+        .into_iter()
+        .map(|(_, _, elf)| elf)
+        .collect()
+}
+
+/// One row per elf, in input order, with its rank by total calories (ties share a rank).
+/// Only the per-elf summaries are kept, never the individual items.
+pub fn report(elves: impl Iterator<Item = Elf>) -> String {
+    let elves: Vec<Elf> = elves.collect();
+    let mut totals: Vec<usize> = elves.iter().map(|e| e.total).collect();
+    totals.sort_by_key(|&t| Reverse(t));
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{:>6} {:>6} {:>6} {:>8} {:>8} {:>10} {:>8}",
+        "Elf", "Items", "Min", "Max", "Mean", "Total", "Rank"
+    )
+    .unwrap();
+    for elf in &elves {
+        let rank = totals.partition_point(|&t| t > elf.total) + 1;
+        writeln!(
+            out,
+            "{:>6} {:>6} {:>6} {:>8} {:>8.1} {:>10} {:>8}",
+            elf.index,
+            elf.items,
+            elf.min,
+            elf.max,
+            elf.mean(),
+            elf.total,
+            rank
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::read_lines_panicky;
+
+    #[test]
+    fn test_elves() {
+        let elves: Vec<_> = elves(read_lines_panicky("test_input.txt")).collect();
+        assert_eq!(5, elves.len());
+        assert_eq!(
+            Elf {
+                index: 4,
+                items: 3,
+                total: 24000,
+                min: 7000,
+                max: 9000
+            },
+            elves[3]
+        );
+        assert_eq!(8000.0, elves[3].mean());
+    }
+
+    #[test]
+    fn test_elves_skips_extra_blank_lines() {
+        let lines = ["", "1", "2", "", "", "3", ""];
+        let totals: Vec<_> = elves(lines).map(|e| (e.index, e.total)).collect();
+        assert_eq!(vec![(1, 3), (2, 3)], totals);
+    }
+
+    /// Every bad line in the inventory, found in a single pass.
+    fn validate(lines: &[&str]) -> Vec<BadLine> {
+        let mut elves = elves(lines);
+        elves.by_ref().for_each(drop);
+        elves.into_bad_lines()
+    }
+
+    fn bad_line(line: usize, elf: usize, text: &str) -> (usize, usize, String) {
+        (line, elf, text.to_owned())
+    }
+
+    fn bad_lines(lines: &[&str]) -> Vec<(usize, usize, String)> {
+        validate(lines)
+            .into_iter()
+            .map(|b| (b.line, b.elf, b.text))
+            .collect()
+    }
+
+    #[test]
+    fn test_whitespace_is_tolerated() {
+        let lines = ["1000\r", "  2000 ", "\t3000", "\r", "   ", "4000\r"];
+        let totals: Vec<_> = elves(lines).map(|e| (e.index, e.total)).collect();
+        assert_eq!(vec![(1, 6000), (2, 4000)], totals);
+        assert!(validate(&lines).is_empty());
+    }
+
+    #[test]
+    fn test_reports_every_bad_line() {
+        let lines = ["1000", "-200", "", "3000", "12 34", "", "4000", "x"];
+        assert_eq!(
+            vec![
+                bad_line(2, 1, "-200"),
+                bad_line(5, 2, "12 34"),
+                bad_line(8, 3, "x")
+            ],
+            bad_lines(&lines)
+        );
+    }
+
+    #[test]
+    fn test_bad_lines_are_skipped() {
+        let lines = ["1000", "oops", "2000", "", "3000"];
+        let mut elves = elves(lines);
+        let totals: Vec<_> = elves.by_ref().map(|e| (e.items, e.total)).collect();
+        assert_eq!(vec![(2, 3000), (1, 3000)], totals);
+        let bad_lines = elves.into_bad_lines();
+        assert_eq!(1, bad_lines.len());
+        assert_eq!(
+            "line 2 (elf 1): `oops` is not a calorie count: invalid digit found in string",
+            bad_lines[0].to_string()
+        );
+    }
+
+    #[test]
+    fn test_all_bad_elf_keeps_its_index() {
+        let lines = ["100", "", "abc", "def", "", "", "300"];
+        let elves: Vec<_> = elves(lines).map(|e| (e.index, e.total)).collect();
+        assert_eq!(vec![(1, 100), (3, 300)], elves);
+        assert_eq!(
+            vec![bad_line(3, 2, "abc"), bad_line(4, 2, "def")],
+            bad_lines(&lines)
+        );
+    }
+
+    #[test]
+    fn test_overflowing_and_empty_values() {
+        let lines = ["99999999999999999999999", "1", "", "+5", "0x10"];
+        assert_eq!(
+            vec![
+                bad_line(1, 1, "99999999999999999999999"),
+                bad_line(5, 2, "0x10")
+            ],
+            bad_lines(&lines)
+        );
+    }
+
+    #[test]
+    fn test_top_k() {
+        assert_eq!(vec![9, 7, 5], top_k([5, 1, 9, 3, 7].into_iter(), 3));
+        assert_eq!(vec![2, 1], top_k([1, 2].into_iter(), 5));
+        assert!(top_k([1, 2].into_iter(), 0).is_empty());
+    }
+
+    #[test]
+    fn test_top_elves() {
+        let top = top_elves(elves(read_lines_panicky("test_input.txt")), 2);
+        let top: Vec<_> = top.iter().map(|e| (e.index, e.total)).collect();
+        assert_eq!(vec![(4, 24000), (3, 11000)], top);
+    }
+
+    #[test]
+    fn test_report() {
+        let report = report(elves(read_lines_panicky("test_input.txt")));
+        let lines: Vec<_> = report.lines().collect();
+        assert_eq!(6, lines.len());
+        assert_eq!(
+            "     1      3   1000     3000   2000.0       6000        4",
+            lines[1]
+        );
+        assert_eq!(
+            "     4      3   7000     9000   8000.0      24000        1",
+            lines[4]
+        );
+    }
+}
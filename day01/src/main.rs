@@ -1,56 +1,109 @@
-use helpers::read_lines_panicky;
+use elves::{BadLine, Elf};
+use helpers::{Args, Input, Results};
 
+mod elves;
+
+// The input is streamed, and read only once, so inventories far larger than memory work,
+// including from stdin. Bad lines are collected along the way and checked before anything
+// is printed.
 fn main() {
-    let lines = get_lines("input.txt");
-    println!("Part 1: {}", part1(&lines));
-    println!("Part 2: {}", part2(&lines));
+    let args = Args::parse_with(env!("CARGO_MANIFEST_DIR"), &["report", "skip-bad", "top="]);
+    let top = args.parsed_option("top");
+    let mut input = Input::open_panicky(&args.input);
+    let mut elves = elves::elves(input.lines_panicky());
+
+    if args.flag("report") {
+        let report = elves::report(elves.by_ref());
+        check_inventory(&args, elves.into_bad_lines());
+        print!("{}", report);
+        return;
+    }
+    if let Some(k) = top {
+        let top = elves::top_elves(elves.by_ref(), k);
+        check_inventory(&args, elves.into_bad_lines());
+        for (i, elf) in top.iter().enumerate() {
+            println!("{:>3}. Elf {}: {}", i + 1, elf.index, elf.total);
+        }
+        println!("Total: {}", top.iter().map(|e| e.total).sum::<usize>());
+        return;
+    }
+
+    let mut results = Results::streamed(1, &args);
+    let mut top = Vec::new();
+    results.part(1, || {
+        top = top_three(elves.by_ref());
+        part1(&top)
+    });
+    results.part(2, || part2(&top));
+    check_inventory(&args, elves.into_bad_lines());
+    results.set_input_hash(input.hash());
+    results.print();
 }
 
-fn get_lines(path: &str) -> Vec<String> {
-    read_lines_panicky(path).collect()
+// Reports every malformed line. They are fatal unless `--skip-bad` is passed, in which case
+// they are left out of the totals.
+fn check_inventory(args: &Args, bad_lines: Vec<BadLine>) {
+    if bad_lines.is_empty() {
+        return;
+    }
+
+    let skip_bad = args.flag("skip-bad");
+    let level = if skip_bad { "warning" } else { "error" };
+    for bad_line in &bad_lines {
+        eprintln!("{}: {}", level, bad_line);
+    }
+    if !skip_bad {
+        eprintln!(
+            "error: {} malformed inventory lines (pass --skip-bad to ignore them)",
+            bad_lines.len()
+        );
+        std::process::exit(1);
+    }
 }
 
-fn part1(lines: &[String]) -> usize {
-    parse_group_sums(lines).max().unwrap()
+/// The three largest totals, largest first, which answer both parts in one pass.
+fn top_three(elves: impl Iterator<Item = Elf>) -> Vec<usize> {
+    elves::top_k(elves.map(|elf| elf.total), 3)
 }
 
-fn part2(lines: &[String]) -> usize {
-    let mut lines = parse_group_sums(lines).collect::<Vec<_>>();
-    lines.sort();
-    lines.iter().rev().take(3).sum()
+fn part1(top: &[usize]) -> usize {
+    top[0]
 }
 
-fn parse_group_sums(lines: &[String]) -> impl Iterator<Item = usize> + '_ {
-    lines
-        .split(|l| l.is_empty())
-        .map(|group| group.into_iter().map(|l| l.parse::<usize>().unwrap()).sum())
+fn part2(top: &[usize]) -> usize {
+    top.iter().sum()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use helpers::read_lines_panicky;
+
+    fn top_three_of(path: &str) -> Vec<usize> {
+        top_three(elves::elves(read_lines_panicky(path)))
+    }
 
     #[test]
     fn part1_sample() {
-        let lines = get_lines("test_input.txt");
-        assert_eq!(24000, part1(&lines));
+        let top = top_three_of("test_input.txt");
+        assert_eq!(24000, part1(&top));
     }
 
     #[test]
     fn part1_final() {
-        let lines = get_lines("input.txt");
-        assert_eq!(67016, part1(&lines));
+        let top = top_three_of("input.txt");
+        assert_eq!(67016, part1(&top));
     }
 
     #[test]
     fn part2_sample() {
-        let lines = get_lines("test_input.txt");
-        assert_eq!(45000, part2(&lines));
+        let top = top_three_of("test_input.txt");
+        assert_eq!(45000, part2(&top));
     }
 
     #[test]
     fn part2_final() {
-        let lines = get_lines("input.txt");
-        assert_eq!(200116, part2(&lines));
+        let top = top_three_of("input.txt");
+        assert_eq!(200116, part2(&top));
     }
 }
@@ -0,0 +1,398 @@
+use std::collections::HashMap;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct OutcomeScores {
+    pub lose: usize,
+    pub draw: usize,
+    pub win: usize,
+}
+
+impl OutcomeScores {
+    pub fn score(&self, outcome: Outcome) -> usize {
+        match outcome {
+            Outcome::Lose => self.lose,
+            Outcome::Draw => self.draw,
+            Outcome::Win => self.win,
+        }
+    }
+}
+
+/// A cyclic dominance game with an odd number of moves, numbered in cycle order. Each move
+/// beats the (n - 1) / 2 moves before it and loses to the (n - 1) / 2 moves after it, so
+/// with `[Rock, Paper, Scissors]` paper beats rock, scissors beats paper and rock beats
+/// scissors.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Rules {
+    names: Vec<String>,
+    shape_scores: Vec<usize>,
+    outcome_scores: OutcomeScores,
+}
+
+impl Rules {
+    pub fn new(names: &[&str], shape_scores: &[usize], outcome_scores: OutcomeScores) -> Self {
+        assert!(
+            names.len() >= 3 && names.len() % 2 == 1,
+            "A cyclic game needs an odd number of moves, at least 3"
+        );
+        assert_eq!(
+            names.len(),
+            shape_scores.len(),
+            "Every move needs a shape score"
+        );
+
+        Self {
+            names: names.iter().map(|&n| n.to_owned()).collect(),
+            shape_scores: shape_scores.to_vec(),
+            outcome_scores,
+        }
+    }
+
+    pub fn rock_paper_scissors() -> Self {
+        Self::new(
+            &["Rock", "Paper", "Scissors"],
+            &[1, 2, 3],
+            OutcomeScores {
+                lose: 0,
+                draw: 3,
+                win: 6,
+            },
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn name(&self, shape: usize) -> &str {
+        &self.names[shape]
+    }
+
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    pub fn outcome(&self, me: usize, opponent: usize) -> Outcome {
+        let n = self.len();
+        let ahead = (me + n - opponent) % n;
+        if ahead == 0 {
+            Outcome::Draw
+        } else if ahead <= n / 2 {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    pub fn score(&self, me: usize, opponent: usize) -> usize {
+        self.shape_scores[me] + self.outcome_scores.score(self.outcome(me, opponent))
+    }
+
+    /// The move that gets `outcome` against `opponent`. With more than three moves several
+    /// may qualify, in which case the one with the best shape score is chosen.
+    pub fn required_for(&self, opponent: usize, outcome: Outcome) -> usize {
+        (0..self.len())
+            .filter(|&me| self.outcome(me, opponent) == outcome)
+            .max_by_key(|&me| (self.shape_scores[me], usize::MAX - me))
+            .unwrap()
+    }
+}
+
+/// How the second column of the strategy guide is read.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Response {
+    Play(usize),
+    Aim(Outcome),
+}
+
+/// A set of rules plus the symbols used for each column of the strategy guide.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub rules: Rules,
+    opponent: HashMap<String, usize>,
+    response: HashMap<String, Response>,
+}
+
+impl Config {
+    pub fn new(rules: Rules, opponent: &[(&str, usize)], response: &[(&str, Response)]) -> Self {
+        for &(symbol, shape) in opponent {
+            assert!(shape < rules.len(), "Unknown move for symbol {}", symbol);
+        }
+        for &(symbol, response) in response {
+            if let Response::Play(shape) = response {
+                assert!(shape < rules.len(), "Unknown move for symbol {}", symbol);
+            }
+        }
+
+        Self {
+            rules,
+            opponent: opponent.iter().map(|&(s, m)| (s.to_owned(), m)).collect(),
+            response: response.iter().map(|&(s, r)| (s.to_owned(), r)).collect(),
+        }
+    }
+
+    /// The guide's second column is the shape to play.
+    pub fn part1() -> Self {
+        Self::new(
+            Rules::rock_paper_scissors(),
+            &[("A", 0), ("B", 1), ("C", 2)],
+            &[
+                ("X", Response::Play(0)),
+                ("Y", Response::Play(1)),
+                ("Z", Response::Play(2)),
+            ],
+        )
+    }
+
+    /// The guide's second column is the outcome to aim for.
+    pub fn part2() -> Self {
+        Self::new(
+            Rules::rock_paper_scissors(),
+            &[("A", 0), ("B", 1), ("C", 2)],
+            &[
+                ("X", Response::Aim(Outcome::Lose)),
+                ("Y", Response::Aim(Outcome::Draw)),
+                ("Z", Response::Aim(Outcome::Win)),
+            ],
+        )
+    }
+
+    /// Reads a rules file such as:
+    ///
+    /// ```text
+    /// moves: Rock Paper Scissors
+    /// shape scores: 1 2 3
+    /// outcome scores: 0 3 6
+    /// opponent: A=Rock B=Paper C=Scissors
+    /// response: X=Lose Y=Draw Z=Win
+    /// ```
+    ///
+    /// Moves are listed in cycle order (see [`Rules`]), and a response is either a move or
+    /// one of `Lose`, `Draw` and `Win`.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut fields = HashMap::new();
+        for line in input.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("Expected `key: value`, got `{}`", line))?;
+            fields.insert(key.trim(), value.split_whitespace().collect::<Vec<_>>());
+        }
+        let field = |key: &str| {
+            fields
+                .get(key)
+                .map(Vec::as_slice)
+                .ok_or_else(|| format!("Missing `{}`", key))
+        };
+        let numbers = |key: &str| -> Result<Vec<usize>, String> {
+            field(key)?
+                .iter()
+                .map(|n| {
+                    n.parse()
+                        .map_err(|_| format!("Bad number `{}` in `{}`", n, key))
+                })
+                .collect()
+        };
+
+        let names = field("moves")?;
+        let shape_scores = numbers("shape scores")?;
+        let outcome_scores = match numbers("outcome scores")?[..] {
+            [lose, draw, win] => OutcomeScores { lose, draw, win },
+            _ => return Err("`outcome scores` needs lose, draw and win scores".to_owned()),
+        };
+        if names.len() < 3 || names.len() % 2 == 0 {
+            return Err("A cyclic game needs an odd number of moves, at least 3".to_owned());
+        }
+        if names.len() != shape_scores.len() {
+            return Err("Every move needs a shape score".to_owned());
+        }
+        let rules = Rules::new(names, &shape_scores, outcome_scores);
+
+        let symbols = |key: &str| -> Result<Vec<(&str, &str)>, String> {
+            field(key)?
+                .iter()
+                .map(|pair| {
+                    pair.split_once('=')
+                        .ok_or_else(|| format!("Expected `symbol=value` in `{}`", key))
+                })
+                .collect()
+        };
+        let shape = |name: &str| {
+            rules
+                .position(name)
+                .ok_or_else(|| format!("Unknown move `{}`", name))
+        };
+        let opponent = symbols("opponent")?
+            .into_iter()
+            .map(|(symbol, name)| Ok((symbol, shape(name)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        let response = symbols("response")?
+            .into_iter()
+            .map(|(symbol, value)| {
+                let response = match value {
+                    "Lose" => Response::Aim(Outcome::Lose),
+                    "Draw" => Response::Aim(Outcome::Draw),
+                    "Win" => Response::Aim(Outcome::Win),
+                    name => Response::Play(shape(name)?),
+                };
+                Ok((symbol, response))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self::new(rules, &opponent, &response))
+    }
+
+    pub fn opponent(&self, symbol: &str) -> Option<usize> {
+        self.opponent.get(symbol).copied()
+    }
+
+    /// The opponent's move and mine for one line of the guide.
+    pub fn parse_round(&self, line: &str) -> (usize, usize) {
+        let (opponent, response) = line.split_once(' ').expect("Expected two columns");
+        let opponent = *self
+            .opponent
+            .get(opponent)
+            .unwrap_or_else(|| panic!("Unrecognized opponent move {}", opponent));
+        let me = match self.response.get(response) {
+            Some(&Response::Play(shape)) => shape,
+            Some(&Response::Aim(outcome)) => self.rules.required_for(opponent, outcome),
+            None => panic!("Unrecognized response {}", response),
+        };
+        (opponent, me)
+    }
+
+    pub fn score_round(&self, line: &str) -> usize {
+        let (opponent, me) = self.parse_round(line);
+        self.rules.score(me, opponent)
+    }
+
+    pub fn score_guide<S: AsRef<str>>(&self, lines: impl IntoIterator<Item = S>) -> usize {
+        lines
+            .into_iter()
+            .map(|l| self.score_round(l.as_ref()))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpsls() -> Config {
+        let input = std::fs::read_to_string("rpsls_rules.txt").unwrap();
+        Config::parse(&input).unwrap()
+    }
+
+    #[test]
+    fn test_rock_paper_scissors() {
+        let rules = Rules::rock_paper_scissors();
+        let [rock, paper, scissors] = [0, 1, 2];
+        assert_eq!(Outcome::Win, rules.outcome(paper, rock));
+        assert_eq!(Outcome::Win, rules.outcome(scissors, paper));
+        assert_eq!(Outcome::Win, rules.outcome(rock, scissors));
+        assert_eq!(Outcome::Lose, rules.outcome(rock, paper));
+        assert_eq!(Outcome::Draw, rules.outcome(scissors, scissors));
+        assert_eq!(scissors, rules.required_for(rock, Outcome::Lose));
+        assert_eq!(8, rules.score(paper, rock));
+    }
+
+    #[test]
+    fn test_rock_paper_scissors_lizard_spock() {
+        let rules = rpsls().rules;
+        let shape = |name| rules.position(name).unwrap();
+        let beats = [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ];
+        for (winner, loser) in beats {
+            assert_eq!(Outcome::Win, rules.outcome(shape(winner), shape(loser)));
+            assert_eq!(Outcome::Lose, rules.outcome(shape(loser), shape(winner)));
+        }
+
+        // Paper and Spock both beat Rock; Spock has the better shape score
+        assert_eq!(
+            shape("Spock"),
+            rules.required_for(shape("Rock"), Outcome::Win)
+        );
+    }
+
+    #[test]
+    fn test_every_game_is_balanced() {
+        for n in [3, 5, 7, 9] {
+            let names: Vec<_> = (0..n).map(|i| i.to_string()).collect();
+            let names: Vec<&str> = names.iter().map(String::as_str).collect();
+            let rules = Rules::new(
+                &names,
+                &vec![1; n],
+                Rules::rock_paper_scissors().outcome_scores,
+            );
+            for me in 0..n {
+                let wins = (0..n)
+                    .filter(|&op| rules.outcome(me, op) == Outcome::Win)
+                    .count();
+                assert_eq!(n / 2, wins);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let input = "\
+moves: Rock Paper Scissors
+shape scores: 1 2 3
+outcome scores: 0 3 6
+opponent: A=Rock B=Paper C=Scissors
+response: X=Lose Y=Draw Z=Win
+";
+        let config = Config::parse(input).unwrap();
+        assert_eq!(Config::part2().rules, config.rules);
+        assert_eq!(12, config.score_guide(["A Y", "B X", "C Z"]));
+
+        assert_eq!(
+            Err("Unknown move `Paper`".to_owned()),
+            Config::parse(&input.replace("Paper Scissors", "Papyrus Scissors")).map(|_| ())
+        );
+        assert_eq!(
+            Err("A cyclic game needs an odd number of moves, at least 3".to_owned()),
+            Config::parse(&input.replace("Scissors\n", "Scissors Well\n")).map(|_| ())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "odd number of moves")]
+    fn test_even_games_are_rejected() {
+        Rules::new(
+            &["A", "B", "C", "D"],
+            &[1, 2, 3, 4],
+            Rules::rock_paper_scissors().outcome_scores,
+        );
+    }
+
+    #[test]
+    fn test_config() {
+        assert_eq!(8, Config::part1().score_round("A Y"));
+        assert_eq!(4, Config::part2().score_round("A Y"));
+        assert_eq!(15, Config::part1().score_guide(["A Y", "B X", "C Z"]));
+
+        let spock = rpsls();
+        // Lizard (4 points) beats Spock, plus 6 for the win
+        assert_eq!(10, spock.score_round("E Z"));
+        assert_eq!(4 + 3, spock.score_round("D Lizard"));
+    }
+}
@@ -0,0 +1,186 @@
+use std::{
+    collections::BTreeSet,
+    time::{Duration, Instant},
+};
+
+use crate::{engine::Config, original, shorter};
+
+/// One way of solving one part of the puzzle, so that solutions can be checked against each
+/// other and timed side by side.
+pub struct Implementation {
+    pub name: &'static str,
+    pub part: u32,
+    pub solve: fn(&[String]) -> usize,
+}
+
+pub const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation {
+        name: "original",
+        part: 1,
+        solve: |lines| original::score_guide_part1(lines),
+    },
+    Implementation {
+        name: "engine",
+        part: 1,
+        solve: |lines| Config::part1().score_guide(lines),
+    },
+    Implementation {
+        name: "shorter",
+        part: 1,
+        solve: |lines| shorter::score_guide_part1(lines),
+    },
+    Implementation {
+        name: "original",
+        part: 2,
+        solve: |lines| original::score_guide_part2(lines),
+    },
+    Implementation {
+        name: "engine",
+        part: 2,
+        solve: |lines| Config::part2().score_guide(lines),
+    },
+    Implementation {
+        name: "shorter",
+        part: 2,
+        solve: |lines| shorter::score_guide_part2(lines),
+    },
+];
+
+pub struct Run {
+    pub implementation: &'static Implementation,
+    pub answer: usize,
+    pub elapsed: Duration,
+}
+
+/// Runs every registered implementation on the same guide.
+pub fn run_all(lines: &[String]) -> Vec<Run> {
+    IMPLEMENTATIONS
+        .iter()
+        .map(|implementation| {
+            let now = Instant::now();
+            let answer = (implementation.solve)(lines);
+            Run {
+                implementation,
+                answer,
+                elapsed: now.elapsed(),
+            }
+        })
+        .collect()
+}
+
+/// The parts whose implementations didn't all give the same answer.
+pub fn disagreements(runs: &[Run]) -> Vec<u32> {
+    let parts: BTreeSet<u32> = runs.iter().map(|r| r.implementation.part).collect();
+    parts
+        .into_iter()
+        .filter(|&part| {
+            let mut answers = runs
+                .iter()
+                .filter(|r| r.implementation.part == part)
+                .map(|r| r.answer);
+            let first = answers.next();
+            answers.any(|a| Some(a) != first)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::read_lines_panicky;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// A strategy guide of `rounds` random lines.
+    fn random_guide(rng: &mut impl Rng, rounds: usize) -> Vec<String> {
+        (0..rounds)
+            .map(|_| {
+                let opponent = rng.gen_range(b'A'..=b'C') as char;
+                let response = rng.gen_range(b'X'..=b'Z') as char;
+                format!("{} {}", opponent, response)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_registry_covers_both_parts() {
+        for part in [1, 2] {
+            let count = IMPLEMENTATIONS.iter().filter(|i| i.part == part).count();
+            assert!(count >= 2, "part {} has {} implementations", part, count);
+        }
+    }
+
+    #[test]
+    fn test_implementations_on_input() {
+        let lines: Vec<String> = read_lines_panicky("input.txt").collect();
+        let runs = run_all(&lines);
+        for run in &runs {
+            let expected = if run.implementation.part == 1 {
+                9759
+            } else {
+                12429
+            };
+            assert_eq!(expected, run.answer, "{}", run.implementation.name);
+        }
+    }
+
+    #[test]
+    fn test_implementations_agree_on_random_guides() {
+        let mut rng = StdRng::seed_from_u64(2022);
+        for _ in 0..5000 {
+            let rounds = rng.gen_range(0..100);
+            let guide = random_guide(&mut rng, rounds);
+            let runs = run_all(&guide);
+            assert!(
+                disagreements(&runs).is_empty(),
+                "implementations disagree on guide {:?}: {:?}",
+                guide,
+                runs.iter()
+                    .map(|r| (r.implementation.name, r.implementation.part, r.answer))
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_disagreements() {
+        let run = |implementation, answer| Run {
+            implementation,
+            answer,
+            elapsed: Duration::ZERO,
+        };
+        let mut runs: Vec<_> = IMPLEMENTATIONS
+            .iter()
+            .map(|i| run(i, i.part as usize))
+            .collect();
+        assert!(disagreements(&runs).is_empty());
+
+        // Whatever order the runs are in, each part is only reported once.
+        runs.sort_by_key(|r| r.implementation.name);
+        let last = runs
+            .iter()
+            .rposition(|r| r.implementation.part == 2)
+            .unwrap();
+        runs[last].answer = 3;
+        assert_eq!(vec![2], disagreements(&runs));
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_implementations() {
+        let guide = random_guide(&mut StdRng::seed_from_u64(2022), 1_000_000);
+        let mut totals = vec![Duration::ZERO; IMPLEMENTATIONS.len()];
+        for _ in 0..10 {
+            for (total, run) in totals.iter_mut().zip(run_all(&guide)) {
+                *total += run.elapsed;
+            }
+        }
+        for (implementation, total) in IMPLEMENTATIONS.iter().zip(totals) {
+            println!(
+                "part {} {:<10} {:>12}",
+                implementation.part,
+                implementation.name,
+                format!("{:.2?}", total / 10)
+            );
+        }
+    }
+}
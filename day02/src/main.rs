@@ -1,150 +1,75 @@
-use helpers::read_lines_panicky;
+use engine::Config;
+use helpers::{read_lines_panicky, read_string_panicky, Args, Format, Results};
 
+mod engine;
+mod implementations;
+mod optimizer;
+mod original;
 mod shorter;
 
 fn main() {
-    println!("Part 1: {}", part1("input.txt"));
-    println!("Part 2: {}", part2("input.txt"));
-
-    println!("\r\nTake 2...");
-    println!("Part 1: {}", shorter::part1("input.txt"));
-    println!("Part 2: {}", shorter::part2("input.txt"));
-}
-
-fn part1(path: &str) -> usize {
-    read_lines_panicky(path)
-        .map(|l| Game::parse_part1(&l))
-        .map(|g| g.play())
-        .map(|o| o.score())
-        .sum()
-}
-
-fn part2(path: &str) -> usize {
-    read_lines_panicky(path)
-        .map(|l| Game::parse_part2(&l))
-        .map(|g| g.play())
-        .map(|o| o.score())
-        .sum()
-}
-
-struct Game {
-    opponent: Shoot,
-    me: Shoot,
-}
-
-impl Game {
-    fn play(&self) -> Outcome {
-        match self.me {
-            Shoot::Rock => match self.opponent {
-                Shoot::Rock => Outcome::Draw(Shoot::Rock),
-                Shoot::Paper => Outcome::Lose(Shoot::Rock),
-                Shoot::Scissors => Outcome::Win(Shoot::Rock),
-            },
-            Shoot::Paper => match self.opponent {
-                Shoot::Rock => Outcome::Win(Shoot::Paper),
-                Shoot::Paper => Outcome::Draw(Shoot::Paper),
-                Shoot::Scissors => Outcome::Lose(Shoot::Paper),
-            },
-            Shoot::Scissors => match self.opponent {
-                Shoot::Rock => Outcome::Lose(Shoot::Scissors),
-                Shoot::Paper => Outcome::Win(Shoot::Scissors),
-                Shoot::Scissors => Outcome::Draw(Shoot::Scissors),
-            },
-        }
+    let args = Args::parse_with(
+        env!("CARGO_MANIFEST_DIR"),
+        &["rules=", "compare", "optimize"],
+    );
+    if let Some(path) = args.option("rules") {
+        let config = Config::parse(&read_string_panicky(path)).unwrap_or_else(|e| {
+            eprintln!("error: invalid rules in {}: {}", path, e);
+            std::process::exit(1);
+        });
+        println!(
+            "Score: {}",
+            config.score_guide(read_lines_panicky(&args.input))
+        );
+        return;
     }
-
-    fn parse_part1(s: &str) -> Game {
-        let mut split = s.split(" ");
-        let opponent = Shoot::parse_part1(&split.next().unwrap());
-        let me = Shoot::parse_part1(&split.next().unwrap());
-        Game { opponent, me }
+    if args.flag("compare") {
+        compare(&args.input);
+        return;
     }
-
-    fn parse_part2(s: &str) -> Game {
-        let mut split = s.split(" ");
-        let opponent = Shoot::parse_part1(&split.next().unwrap());
-        let outcome = PlannedOutcome::parse(&split.next().unwrap());
-        let me = required_for_outcome(&opponent, &outcome);
-        Game { opponent, me }
+    if args.flag("optimize") {
+        let config = Config::part1();
+        let analysis = optimizer::analyze(&config, read_lines_panicky(&args.input));
+        print!("{}", optimizer::report(&config, &analysis));
+        return;
     }
-}
 
-enum PlannedOutcome {
-    Win,
-    Draw,
-    Lose,
-}
+    let mut results = Results::new(2, &args);
+    results.part(1, || part1(&args.input));
+    results.part(2, || part2(&args.input));
+    results.print();
 
-impl PlannedOutcome {
-    fn parse(s: &str) -> PlannedOutcome {
-        match s {
-            "X" => PlannedOutcome::Lose,
-            "Y" => PlannedOutcome::Draw,
-            "Z" => PlannedOutcome::Win,
-            _ => panic!("Unrecognized planned outcome"),
-        }
+    if args.format == Format::Text {
+        println!("\r\nTake 2...");
+        println!("Part 1: {}", shorter::part1(&args.input));
+        println!("Part 2: {}", shorter::part2(&args.input));
     }
 }
 
-fn required_for_outcome(opponent: &Shoot, outcome: &PlannedOutcome) -> Shoot {
-    match opponent {
-        Shoot::Rock => match outcome {
-            PlannedOutcome::Win => Shoot::Paper,
-            PlannedOutcome::Draw => Shoot::Rock,
-            PlannedOutcome::Lose => Shoot::Scissors,
-        },
-        Shoot::Paper => match outcome {
-            PlannedOutcome::Win => Shoot::Scissors,
-            PlannedOutcome::Draw => Shoot::Paper,
-            PlannedOutcome::Lose => Shoot::Rock,
-        },
-        Shoot::Scissors => match outcome {
-            PlannedOutcome::Win => Shoot::Rock,
-            PlannedOutcome::Draw => Shoot::Scissors,
-            PlannedOutcome::Lose => Shoot::Paper,
-        },
+/// Runs every registered implementation on the input, failing if any part's answers differ.
+fn compare(path: &str) {
+    let lines: Vec<String> = read_lines_panicky(path).collect();
+    let runs = implementations::run_all(&lines);
+    for run in &runs {
+        println!(
+            "Part {} {:<10} {:>10} ({:.2?})",
+            run.implementation.part, run.implementation.name, run.answer, run.elapsed
+        );
     }
-}
-
-enum Shoot {
-    Rock,
-    Paper,
-    Scissors,
-}
 
-impl Shoot {
-    fn score(&self) -> usize {
-        match self {
-            Shoot::Rock => 1,
-            Shoot::Paper => 2,
-            Shoot::Scissors => 3,
-        }
-    }
-
-    fn parse_part1(s: &str) -> Shoot {
-        match s {
-            "A" | "X" => Shoot::Rock,
-            "B" | "Y" => Shoot::Paper,
-            "C" | "Z" => Shoot::Scissors,
-            _ => panic!("Unrecognized shoot"),
-        }
+    let disagreements = implementations::disagreements(&runs);
+    if !disagreements.is_empty() {
+        eprintln!("error: implementations disagree on part {:?}", disagreements);
+        std::process::exit(1);
     }
 }
 
-enum Outcome {
-    Win(Shoot),
-    Draw(Shoot),
-    Lose(Shoot),
+fn part1(path: &str) -> usize {
+    Config::part1().score_guide(read_lines_panicky(path))
 }
 
-impl Outcome {
-    fn score(&self) -> usize {
-        match self {
-            Outcome::Win(shoot) => 6 + shoot.score(),
-            Outcome::Draw(shoot) => 3 + shoot.score(),
-            Outcome::Lose(shoot) => 0 + shoot.score(),
-        }
-    }
+fn part2(path: &str) -> usize {
+    Config::part2().score_guide(read_lines_panicky(path))
 }
 
 #[cfg(test)]
@@ -0,0 +1,260 @@
+use std::fmt::Write;
+
+use crate::engine::{Config, Outcome};
+
+const OUTCOMES: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+
+/// What the strategy guide is worth under the interpretation given by one ordering of its
+/// response letters.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Interpretation {
+    /// `order[i]` is the move, or the index into lose/draw/win, that letter `i` stands for.
+    pub order: Vec<usize>,
+    pub as_moves: usize,
+    pub as_outcomes: usize,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Analysis {
+    pub letters: Vec<String>,
+    pub opponent_counts: Vec<usize>,
+    /// Expected score per round for always playing each move, against the observed opponent
+    /// frequencies.
+    pub expected_scores: Vec<f64>,
+    /// The best move for each letter, and what the guide scores when read that way.
+    pub best_mapping: Vec<usize>,
+    pub best_mapping_score: usize,
+    pub interpretations: Vec<Interpretation>,
+}
+
+/// Analyses a strategy guide using `config`'s rules and opponent symbols.
+pub fn analyze<S: AsRef<str>>(config: &Config, lines: impl IntoIterator<Item = S>) -> Analysis {
+    let rules = &config.rules;
+    let mut letters: Vec<String> = Vec::new();
+    let mut rounds = Vec::new();
+    for line in lines {
+        let (opponent, letter) = line.as_ref().split_once(' ').expect("Expected two columns");
+        let opponent = config
+            .opponent(opponent)
+            .unwrap_or_else(|| panic!("Unrecognized opponent move {}", opponent));
+        let letter = match letters.iter().position(|l| l == letter) {
+            Some(i) => i,
+            None => {
+                letters.push(letter.to_owned());
+                letters.len() - 1
+            }
+        };
+        rounds.push((opponent, letter));
+    }
+
+    // Keep letters in sorted order so X, Y, Z line up with the puzzle's reading.
+    let mut sorted: Vec<usize> = (0..letters.len()).collect();
+    sorted.sort_by(|&a, &b| letters[a].cmp(&letters[b]));
+    let mut rank = vec![0; letters.len()];
+    for (new, &old) in sorted.iter().enumerate() {
+        rank[old] = new;
+    }
+    let letters: Vec<String> = sorted.iter().map(|&i| letters[i].clone()).collect();
+    let rounds: Vec<(usize, usize)> = rounds.into_iter().map(|(o, l)| (o, rank[l])).collect();
+
+    let mut opponent_counts = vec![0; rules.len()];
+    for &(opponent, _) in &rounds {
+        opponent_counts[opponent] += 1;
+    }
+    let expected_scores = (0..rules.len())
+        .map(|me| {
+            let total: usize = (0..rules.len())
+                .map(|op| opponent_counts[op] * rules.score(me, op))
+                .sum();
+            total as f64 / rounds.len().max(1) as f64
+        })
+        .collect();
+
+    // The guide's score is a sum over letters, so each letter's best move can be picked alone.
+    let letter_score = |letter: usize, me: usize| -> usize {
+        rounds
+            .iter()
+            .filter(|&&(_, l)| l == letter)
+            .map(|&(op, _)| rules.score(me, op))
+            .sum()
+    };
+    let best_mapping: Vec<usize> = (0..letters.len())
+        .map(|letter| {
+            (0..rules.len())
+                .max_by_key(|&me| (letter_score(letter, me), usize::MAX - me))
+                .unwrap()
+        })
+        .collect();
+    let best_mapping_score = best_mapping
+        .iter()
+        .enumerate()
+        .map(|(letter, &me)| letter_score(letter, me))
+        .sum();
+
+    let mut interpretations = Vec::new();
+    if letters.len() == OUTCOMES.len() && rules.len() == OUTCOMES.len() {
+        for order in permutations(letters.len()) {
+            let as_moves = rounds
+                .iter()
+                .map(|&(op, l)| rules.score(order[l], op))
+                .sum();
+            let as_outcomes = rounds
+                .iter()
+                .map(|&(op, l)| rules.score(rules.required_for(op, OUTCOMES[order[l]]), op))
+                .sum();
+            interpretations.push(Interpretation {
+                order,
+                as_moves,
+                as_outcomes,
+            });
+        }
+    }
+
+    Analysis {
+        letters,
+        opponent_counts,
+        expected_scores,
+        best_mapping,
+        best_mapping_score,
+        interpretations,
+    }
+}
+
+/// Every ordering of `0..n`, in lexicographic order.
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+    let mut all = Vec::new();
+    for first in 0..n {
+        for rest in permutations(n - 1) {
+            let mut order = vec![first];
+            order.extend(rest.into_iter().map(|i| if i >= first { i + 1 } else { i }));
+            all.push(order);
+        }
+    }
+    all
+}
+
+pub fn report(config: &Config, analysis: &Analysis) -> String {
+    let rules = &config.rules;
+    let rounds: usize = analysis.opponent_counts.iter().sum();
+    let mut out = String::new();
+
+    writeln!(out, "Opponent moves over {} rounds:", rounds).unwrap();
+    for (shape, &count) in analysis.opponent_counts.iter().enumerate() {
+        let share = 100.0 * count as f64 / rounds.max(1) as f64;
+        writeln!(
+            out,
+            "  {:<10} {:>6} ({:.1}%)",
+            rules.name(shape),
+            count,
+            share
+        )
+        .unwrap();
+    }
+
+    writeln!(out, "\nExpected score per round when always playing:").unwrap();
+    for (shape, score) in analysis.expected_scores.iter().enumerate() {
+        writeln!(out, "  {:<10} {:>6.3}", rules.name(shape), score).unwrap();
+    }
+
+    let mapping: Vec<_> = analysis
+        .letters
+        .iter()
+        .zip(&analysis.best_mapping)
+        .map(|(letter, &shape)| format!("{}={}", letter, rules.name(shape)))
+        .collect();
+    writeln!(
+        out,
+        "\nBest fixed mapping: {} scores {}",
+        mapping.join(" "),
+        analysis.best_mapping_score
+    )
+    .unwrap();
+
+    if !analysis.interpretations.is_empty() {
+        writeln!(out, "\nEvery ordering of the letters:").unwrap();
+        writeln!(out, "  {:<44} {:>9} {:>11}", "", "as moves", "as outcomes").unwrap();
+        for interpretation in &analysis.interpretations {
+            let reading: Vec<_> = analysis
+                .letters
+                .iter()
+                .zip(&interpretation.order)
+                .map(|(letter, &i)| format!("{}={}/{:?}", letter, rules.name(i), OUTCOMES[i]))
+                .collect();
+            writeln!(
+                out,
+                "  {:<44} {:>9} {:>11}",
+                reading.join(" "),
+                interpretation.as_moves,
+                interpretation.as_outcomes
+            )
+            .unwrap();
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::read_lines_panicky;
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(
+            vec![
+                vec![0, 1, 2],
+                vec![0, 2, 1],
+                vec![1, 0, 2],
+                vec![1, 2, 0],
+                vec![2, 0, 1],
+                vec![2, 1, 0]
+            ],
+            permutations(3)
+        );
+    }
+
+    #[test]
+    fn test_analyze_sample() {
+        let analysis = analyze(&Config::part1(), read_lines_panicky("test_input.txt"));
+        assert_eq!(vec!["X", "Y", "Z"], analysis.letters);
+        assert_eq!(vec![1, 1, 1], analysis.opponent_counts);
+        assert_eq!(vec![4.0, 5.0, 6.0], analysis.expected_scores);
+        // Y vs Rock -> Paper, X vs Paper -> Scissors, Z vs Scissors -> Rock
+        assert_eq!(vec![2, 1, 0], analysis.best_mapping);
+        assert_eq!(24, analysis.best_mapping_score);
+        assert_eq!(
+            Interpretation {
+                order: vec![0, 1, 2],
+                as_moves: 15,
+                as_outcomes: 12
+            },
+            analysis.interpretations[0]
+        );
+    }
+
+    #[test]
+    fn test_interpretations_match_parts() {
+        let analysis = analyze(&Config::part1(), read_lines_panicky("input.txt"));
+        let identity = &analysis.interpretations[0];
+        assert_eq!(9759, identity.as_moves);
+        assert_eq!(12429, identity.as_outcomes);
+        assert_eq!(6, analysis.interpretations.len());
+        assert!(analysis
+            .interpretations
+            .iter()
+            .all(|i| i.as_moves <= analysis.best_mapping_score));
+    }
+
+    #[test]
+    fn test_report() {
+        let config = Config::part1();
+        let report = report(&config, &analyze(&config, ["A Y", "B X", "C Z"]));
+        assert!(report.contains("  Scissors    6.000\n"), "{}", report);
+        assert!(report.contains("Best fixed mapping: X=Scissors Y=Paper Z=Rock scores 24\n"));
+        assert!(report.contains("  X=Rock/Lose Y=Paper/Draw Z=Scissors/Win"));
+    }
+}
@@ -1,19 +1,30 @@
 use helpers::read_lines_panicky;
 
 pub fn part1(path: &str) -> usize {
-    read_lines_panicky(path)
-        .map(|line| split(&line))
+    score_guide_part1(read_lines_panicky(path))
+}
+
+pub fn part2(path: &str) -> usize {
+    score_guide_part2(read_lines_panicky(path))
+}
+
+pub fn score_guide_part1<S: AsRef<str>>(lines: impl IntoIterator<Item = S>) -> usize {
+    lines
+        .into_iter()
+        .map(|line| split(line.as_ref()))
         .map(|(op, me)| score_part1(op, me))
         .sum()
 }
 
-pub fn part2(path: &str) -> usize {
-    read_lines_panicky(path)
-        .map(|line| split(&line))
+pub fn score_guide_part2<S: AsRef<str>>(lines: impl IntoIterator<Item = S>) -> usize {
+    lines
+        .into_iter()
+        .map(|line| split(line.as_ref()))
         .map(|(op, me)| score_part2(op, me))
         .sum()
 }
 
+#[allow(clippy::iter_skip_next)]
 fn split(line: &str) -> (char, char) {
     let mut chars = line.chars();
     let op = chars.next().unwrap();
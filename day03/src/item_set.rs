@@ -0,0 +1,155 @@
+use std::ops::{BitAnd, BitOr};
+
+/// A set of rucksack items, stored as one bit per item at the position of its priority.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct ItemSet(u64);
+
+/// `a`–`z` are worth 1–26 and `A`–`Z` are worth 27–52; anything else isn't an item.
+pub fn priority(c: char) -> Option<usize> {
+    match c {
+        'a'..='z' => Some(c as usize - 'a' as usize + 1),
+        'A'..='Z' => Some(c as usize - 'A' as usize + 27),
+        _ => None,
+    }
+}
+
+fn item(priority: usize) -> char {
+    match priority {
+        1..=26 => (b'a' + (priority - 1) as u8) as char,
+        _ => (b'A' + (priority - 27) as u8) as char,
+    }
+}
+
+impl ItemSet {
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    /// Every set is a subset of this one.
+    pub fn all() -> Self {
+        Self(((1 << 52) - 1) << 1)
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let priority = priority(c).unwrap_or_else(|| panic!("`{}` is not an item", c));
+        self.0 |= 1 << priority;
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// The items in the set, in priority order.
+    pub fn iter(&self) -> Iter {
+        Iter(self.0)
+    }
+}
+
+impl FromIterator<char> for ItemSet {
+    fn from_iter<T: IntoIterator<Item = char>>(iter: T) -> Self {
+        let mut set = Self::new();
+        for c in iter {
+            set.insert(c);
+        }
+        set
+    }
+}
+
+impl From<&str> for ItemSet {
+    fn from(items: &str) -> Self {
+        items.chars().collect()
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(rhs)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+pub struct Iter(u64);
+
+impl Iterator for Iter {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.0 == 0 {
+            return None;
+        }
+        let priority = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(item(priority))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl IntoIterator for ItemSet {
+    type Item = char;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority() {
+        assert_eq!(Some(1), priority('a'));
+        assert_eq!(Some(26), priority('z'));
+        assert_eq!(Some(27), priority('A'));
+        assert_eq!(Some(52), priority('Z'));
+        assert_eq!(None, priority('1'));
+        assert_eq!(None, priority('é'));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let first = ItemSet::from("vJrwpWtwJgWr");
+        let second = ItemSet::from("hcsFMMfFFhFp");
+        assert_eq!(vec!['p'], (first & second).iter().collect::<Vec<_>>());
+        assert_eq!(8, first.len());
+        assert_eq!(14, (first | second).len());
+        assert_eq!(0, ItemSet::new().len());
+    }
+
+    #[test]
+    fn test_iter_round_trips() {
+        let items = "azAZmM";
+        let set = ItemSet::from(items);
+        assert_eq!("amzAMZ", set.iter().collect::<String>());
+        assert_eq!(52, ItemSet::all().len());
+        assert_eq!(ItemSet::all(), ItemSet::all().iter().collect());
+    }
+
+    #[test]
+    #[should_panic(expected = "`1` is not an item")]
+    fn test_insert_rejects_non_items() {
+        let _ = ItemSet::from("ab1");
+    }
+}
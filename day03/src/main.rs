@@ -1,56 +1,108 @@
-use std::collections::HashSet;
+use std::num::NonZeroUsize;
 
-use helpers::read_lines_panicky;
+use helpers::{read_lines_panicky, Args, Results};
+use item_set::{priority, ItemSet};
+
+mod item_set;
+mod validate;
+
+const GROUP_SIZE: usize = 3;
 
 fn main() {
-    println!("Part 1: {}", part1("input.txt"));
-    println!("Part 2: {}", part2("input.txt"));
+    let args = Args::parse_with(env!("CARGO_MANIFEST_DIR"), &["group-size=", "validate"]);
+    // A group of 0 is rejected like any other malformed value, rather than by the asserts.
+    let group_size = args
+        .parsed_option::<NonZeroUsize>("group-size")
+        .map(NonZeroUsize::get);
+    check_rucksacks(&args, group_size.unwrap_or(GROUP_SIZE));
+
+    let mut results = Results::new(3, &args);
+    results.part(1, || part1(&args.input));
+    results.part(2, || match group_size {
+        Some(group_size) => groups_priority(read_lines_panicky(&args.input), group_size),
+        None => part2(&args.input),
+    });
+    results.print();
+}
+
+// The solution assumes exactly one shared item per rucksack and one badge per group, so any
+// anomalies are reported up front rather than panicking halfway through. `--validate` reports
+// them on stdout and stops there.
+fn check_rucksacks(args: &Args, group_size: usize) {
+    let anomalies = validate::validate(read_lines_panicky(&args.input), group_size);
+    if args.flag("validate") {
+        for anomaly in &anomalies {
+            println!("{}", anomaly);
+        }
+        println!("{} anomalies", anomalies.len());
+        std::process::exit(if anomalies.is_empty() { 0 } else { 1 });
+    }
+
+    if !anomalies.is_empty() {
+        for anomaly in &anomalies {
+            eprintln!("error: {}", anomaly);
+        }
+        eprintln!("error: {} anomalies in the rucksack list", anomalies.len());
+        std::process::exit(1);
+    }
 }
 
 fn part1(path: &str) -> usize {
     read_lines_panicky(path)
-        .map(|line| line.chars().collect::<Vec<_>>())
         .map(|line| rucksack_priority(&line))
         .sum()
 }
 
 fn part2(path: &str) -> usize {
-    let lines = read_lines_panicky(path)
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    lines.chunks(3).map(|chunk| group_priority(chunk)).sum()
+    groups_priority(read_lines_panicky(path), GROUP_SIZE)
 }
 
-fn group_priority(chunk: &[Vec<char>]) -> usize {
-    let first = HashSet::<_>::from_iter(chunk[0].iter());
-    let second = HashSet::<_>::from_iter(chunk[1].iter());
-    let third = HashSet::<_>::from_iter(chunk[2].iter());
-
-    let mut first_two = first.intersection(&second);
-    let &&in_all_three = first_two.find(|&&c| third.contains(c)).unwrap();
-    char_priority(in_all_three)
+/// The sum of the badge priorities of each consecutive group of `group_size` rucksacks.
+fn groups_priority<S: AsRef<str>>(lines: impl IntoIterator<Item = S>, group_size: usize) -> usize {
+    assert!(group_size > 0, "Group size must be positive");
+    let mut total = 0;
+    let mut common = ItemSet::all();
+    let mut in_group = 0;
+    for line in lines {
+        common = common & ItemSet::from(line.as_ref());
+        in_group += 1;
+        if in_group == group_size {
+            total += single_priority(common);
+            common = ItemSet::all();
+            in_group = 0;
+        }
+    }
+    assert!(
+        in_group == 0,
+        "{} rucksacks left over after the last group of {}",
+        in_group,
+        group_size
+    );
+    total
 }
 
-fn rucksack_priority(line: &[char]) -> usize {
-    let halfway = line.len() / 2;
-    let first = &line[..halfway];
-    let second = &line[halfway..];
-
-    let &in_both = first.into_iter().find(|c| second.contains(c)).unwrap();
-    char_priority(in_both)
+fn rucksack_priority(line: &str) -> usize {
+    let (first, second) = line.split_at(line.len() / 2);
+    single_priority(ItemSet::from(first) & ItemSet::from(second))
 }
 
-fn char_priority(c: char) -> usize {
-    if c.is_lowercase() {
-        (c as usize) - ('a' as usize) + 1
-    } else {
-        (c as usize) - ('A' as usize) + 27
+fn single_priority(items: ItemSet) -> usize {
+    match items.len() {
+        0 => panic!("No item in common"),
+        1 => priority(items.iter().next().unwrap()).unwrap(),
+        _ => panic!("More than one item in common"),
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        collections::HashSet,
+        time::{Duration, Instant},
+    };
+
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
     use super::*;
 
     #[test]
@@ -72,4 +124,100 @@ mod tests {
     fn part2_final() {
         assert_eq!(2497, part2("input.txt"));
     }
+
+    #[test]
+    fn test_group_sizes() {
+        assert_eq!(2 + 4, groups_priority(["ab", "bc", "cd", "de"], 2));
+        assert_eq!(50, groups_priority(["abX", "bcX", "cdX", "deX"], 4));
+    }
+
+    #[test]
+    #[should_panic(expected = "1 rucksacks left over after the last group of 3")]
+    fn test_incomplete_group() {
+        groups_priority(["a", "a", "a", "a"], 3);
+    }
+
+    const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    /// `groups` groups of three 48-item rucksacks. Each rucksack draws from its own slice of
+    /// the alphabet, so the only items shared are the group's badge and one item per rucksack
+    /// that appears in both compartments.
+    fn synthetic_rucksacks(groups: usize) -> Vec<String> {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut lines = Vec::new();
+        for _ in 0..groups {
+            let mut items = ITEMS.to_vec();
+            items.shuffle(&mut rng);
+            let (&badge, rest) = items.split_first().unwrap();
+            for chunk in rest.chunks(17) {
+                let (&shared, chunk) = chunk.split_first().unwrap();
+                let (left, right) = chunk.split_at(8);
+                let mut line: Vec<u8> = (0..22).map(|_| *left.choose(&mut rng).unwrap()).collect();
+                line.extend([badge, shared]);
+                line.extend((0..23).map(|_| *right.choose(&mut rng).unwrap()));
+                line.push(shared);
+                lines.push(String::from_utf8(line).unwrap());
+            }
+        }
+        lines
+    }
+
+    fn scan_part1(lines: &[String]) -> usize {
+        lines
+            .iter()
+            .map(|line| {
+                let line: Vec<char> = line.chars().collect();
+                let (first, second) = line.split_at(line.len() / 2);
+                let c = *first.iter().find(|c| second.contains(c)).unwrap();
+                priority(c).unwrap()
+            })
+            .sum()
+    }
+
+    fn hash_set_part2(lines: &[String]) -> usize {
+        lines
+            .chunks(3)
+            .map(|group| {
+                let sets: Vec<HashSet<char>> = group.iter().map(|l| l.chars().collect()).collect();
+                let c = *sets[0]
+                    .iter()
+                    .find(|c| sets[1..].iter().all(|s| s.contains(c)))
+                    .unwrap();
+                priority(c).unwrap()
+            })
+            .sum()
+    }
+
+    fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+        let now = Instant::now();
+        let result = f();
+        (result, now.elapsed())
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_item_sets() {
+        let lines = synthetic_rucksacks(300_000);
+        println!("{} rucksacks", lines.len());
+
+        let (expected, scan) = time(|| scan_part1(&lines));
+        let (actual, bitmask) = time(|| lines.iter().map(|l| rucksack_priority(l)).sum::<usize>());
+        assert_eq!(expected, actual);
+        println!(
+            "Part 1: contains scan {:.2?}, bitmask {:.2?} ({:.1}x)",
+            scan,
+            bitmask,
+            scan.as_secs_f64() / bitmask.as_secs_f64()
+        );
+
+        let (expected, hash_set) = time(|| hash_set_part2(&lines));
+        let (actual, bitmask) = time(|| groups_priority(&lines, 3));
+        assert_eq!(expected, actual);
+        println!(
+            "Part 2: HashSet {:.2?}, bitmask {:.2?} ({:.1}x)",
+            hash_set,
+            bitmask,
+            hash_set.as_secs_f64() / bitmask.as_secs_f64()
+        );
+    }
 }
@@ -0,0 +1,234 @@
+use std::fmt::{self, Display};
+
+use crate::item_set::{priority, ItemSet};
+
+/// Something in the rucksack list that the puzzle's assumptions don't allow for. Line numbers
+/// are 1-based and columns count characters, also from 1.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Anomaly {
+    InvalidItem {
+        line: usize,
+        column: usize,
+        c: char,
+    },
+    OddLength {
+        line: usize,
+        len: usize,
+    },
+    /// Anything other than exactly one item in both compartments.
+    SharedItems {
+        line: usize,
+        items: ItemSet,
+    },
+    /// Anything other than exactly one item carried by the whole group.
+    AmbiguousBadge {
+        first: usize,
+        last: usize,
+        badges: ItemSet,
+    },
+    IncompleteGroup {
+        first: usize,
+        last: usize,
+        expected: usize,
+    },
+}
+
+fn items(items: &ItemSet) -> String {
+    items.iter().collect()
+}
+
+impl Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Anomaly::InvalidItem { line, column, c } => {
+                write!(
+                    f,
+                    "line {}: `{}` at column {} is not an item",
+                    line, c, column
+                )
+            }
+            Anomaly::OddLength { line, len } => write!(
+                f,
+                "line {}: {} items can't be split into two equal compartments",
+                line, len
+            ),
+            Anomaly::SharedItems {
+                line,
+                items: shared,
+            } if shared.len() == 0 => {
+                write!(f, "line {}: no item is in both compartments", line)
+            }
+            Anomaly::SharedItems {
+                line,
+                items: shared,
+            } => write!(
+                f,
+                "line {}: {} items are in both compartments: {}",
+                line,
+                shared.len(),
+                items(shared)
+            ),
+            Anomaly::AmbiguousBadge {
+                first,
+                last,
+                badges,
+            } if badges.len() == 0 => {
+                write!(
+                    f,
+                    "lines {}-{}: no item is common to the group",
+                    first, last
+                )
+            }
+            Anomaly::AmbiguousBadge {
+                first,
+                last,
+                badges,
+            } => write!(
+                f,
+                "lines {}-{}: badge is ambiguous, could be any of {}",
+                first,
+                last,
+                items(badges)
+            ),
+            Anomaly::IncompleteGroup {
+                first,
+                last,
+                expected,
+            } => write!(
+                f,
+                "lines {}-{}: last group has {} rucksacks, expected {}",
+                first,
+                last,
+                last - first + 1,
+                expected
+            ),
+        }
+    }
+}
+
+/// Every anomaly in the list, in line order, taking rucksacks `group_size` at a time.
+/// Invalid characters are reported and then left out of the item checks.
+pub fn validate<S: AsRef<str>>(
+    lines: impl IntoIterator<Item = S>,
+    group_size: usize,
+) -> Vec<Anomaly> {
+    assert!(group_size > 0, "Group size must be positive");
+    let mut anomalies = Vec::new();
+    let mut group = (0, ItemSet::all());
+    let mut line = 0;
+
+    for text in lines {
+        line += 1;
+        let chars: Vec<char> = text.as_ref().chars().collect();
+        for (i, &c) in chars.iter().enumerate() {
+            if priority(c).is_none() {
+                anomalies.push(Anomaly::InvalidItem {
+                    line,
+                    column: i + 1,
+                    c,
+                });
+            }
+        }
+
+        let valid = |chars: &[char]| -> ItemSet {
+            chars
+                .iter()
+                .copied()
+                .filter(|&c| priority(c).is_some())
+                .collect()
+        };
+        if chars.len() % 2 == 1 {
+            anomalies.push(Anomaly::OddLength {
+                line,
+                len: chars.len(),
+            });
+        } else {
+            let (first, second) = chars.split_at(chars.len() / 2);
+            let shared = valid(first) & valid(second);
+            if shared.len() != 1 {
+                anomalies.push(Anomaly::SharedItems {
+                    line,
+                    items: shared,
+                });
+            }
+        }
+
+        group = (group.0 + 1, group.1 & valid(&chars));
+        if group.0 == group_size {
+            if group.1.len() != 1 {
+                anomalies.push(Anomaly::AmbiguousBadge {
+                    first: line + 1 - group_size,
+                    last: line,
+                    badges: group.1,
+                });
+            }
+            group = (0, ItemSet::all());
+        }
+    }
+
+    if group.0 > 0 {
+        anomalies.push(Anomaly::IncompleteGroup {
+            first: line + 1 - group.0,
+            last: line,
+            expected: group_size,
+        });
+    }
+    anomalies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::read_lines_panicky;
+
+    #[test]
+    fn test_valid_input() {
+        assert!(validate(read_lines_panicky("input.txt"), 3).is_empty());
+        assert!(validate(read_lines_panicky("test_input.txt"), 3).is_empty());
+    }
+
+    #[test]
+    fn test_reports_every_anomaly() {
+        let lines = ["abca", "abcab", "abab", "ab1a", "xyzw", "aXbX", "XbcX"];
+        let anomalies: Vec<_> = validate(lines, 3).iter().map(|a| a.to_string()).collect();
+        assert_eq!(
+            vec![
+                "line 2: 5 items can't be split into two equal compartments",
+                "line 3: 2 items are in both compartments: ab",
+                "lines 1-3: badge is ambiguous, could be any of ab",
+                "line 4: `1` at column 3 is not an item",
+                "line 5: no item is in both compartments",
+                "lines 4-6: no item is common to the group",
+                "lines 7-7: last group has 1 rucksacks, expected 3",
+            ],
+            anomalies
+        );
+    }
+
+    #[test]
+    fn test_group_size() {
+        let lines = ["abca", "cdec", "fcgf", "hcih"];
+        assert!(validate(lines, 2).is_empty());
+        assert!(validate(lines, 4).is_empty());
+        assert_eq!(1, validate(lines, 3).len());
+    }
+
+    #[test]
+    fn test_non_ascii_lines() {
+        assert_eq!(
+            vec![
+                Anomaly::InvalidItem {
+                    line: 1,
+                    column: 2,
+                    c: 'é'
+                },
+                Anomaly::InvalidItem {
+                    line: 1,
+                    column: 4,
+                    c: 'é'
+                }
+            ],
+            validate(["aéaé"], 1)
+        );
+    }
+}
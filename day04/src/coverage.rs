@@ -0,0 +1,212 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap};
+
+use helpers::{Interval, IntervalSet};
+
+/// One elf's assignment, with the (1-based) line it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Assignment {
+    pub line: usize,
+    pub sections: Interval,
+}
+
+/// How the whole file's assignments cover the sections between the lowest and highest
+/// section anyone was assigned.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Coverage {
+    pub span: Option<Interval>,
+    pub max_elves: usize,
+    /// The sections that `max_elves` elves are assigned to.
+    pub busiest: IntervalSet,
+    /// Sections within the span that nobody is assigned to.
+    pub uncovered: IntervalSet,
+}
+
+pub fn assignments<S: AsRef<str>>(lines: impl IntoIterator<Item = S>) -> Vec<Assignment> {
+    let mut assignments = Vec::new();
+    for (i, line) in lines.into_iter().enumerate() {
+        for sections in line.as_ref().split(',') {
+            assignments.push(Assignment {
+                line: i + 1,
+                sections: sections.parse().unwrap(),
+            });
+        }
+    }
+    assignments
+}
+
+/// Sweeps across the start and end of every assignment in order, tracking how many elves
+/// cover each stretch of sections in between. O(n log n).
+pub fn coverage(assignments: &[Assignment]) -> Coverage {
+    // An assignment ending at `i64::MAX` never closes: it covers everything to the end.
+    let mut events: Vec<(i64, isize)> = assignments
+        .iter()
+        .flat_map(|a| {
+            let close = a.sections.end().checked_add(1).map(|end| (end, -1));
+            [(a.sections.start(), 1)].into_iter().chain(close)
+        })
+        .collect();
+    events.sort_unstable();
+
+    let mut max_elves = 0;
+    let mut busiest = IntervalSet::new();
+    let mut covered = IntervalSet::new();
+    let mut elves = 0;
+    for (i, &(at, change)) in events.iter().enumerate() {
+        elves += change;
+        if elves == 0 {
+            continue;
+        }
+        // `elves` is the count for every section from here up to the next event.
+        let end = match events.get(i + 1) {
+            Some(&(next, _)) if next == at => continue,
+            Some(&(next, _)) => next - 1,
+            None => i64::MAX,
+        };
+        let stretch = Interval::new(at, end);
+        covered.insert(stretch);
+        let elves = elves as usize;
+        if elves > max_elves {
+            max_elves = elves;
+            busiest = IntervalSet::new();
+        }
+        if elves == max_elves {
+            busiest.insert(stretch);
+        }
+    }
+
+    let span = covered
+        .intervals()
+        .first()
+        .zip(covered.intervals().last())
+        .map(|(first, last)| Interval::new(first.start(), last.end()));
+    let uncovered = span.map_or_else(IntervalSet::new, |span| {
+        IntervalSet::from_iter([span]).difference(&covered)
+    });
+    Coverage {
+        span,
+        max_elves,
+        busiest,
+        uncovered,
+    }
+}
+
+/// Every pair of lines, `(earlier, later)`, where an assignment on one overlaps an assignment
+/// on the other. Assignments are swept in order of their start, keeping the ones still open
+/// in a heap ordered by their end, so each step only visits assignments it overlaps:
+/// O(n log n + k log k) for k overlapping pairs.
+pub fn overlapping_lines(assignments: &[Assignment]) -> Vec<(usize, usize)> {
+    let mut by_start: Vec<&Assignment> = assignments.iter().collect();
+    by_start.sort_unstable_by_key(|a| a.sections.start());
+
+    let mut open: BinaryHeap<Reverse<(i64, usize)>> = BinaryHeap::new();
+    let mut pairs = BTreeSet::new();
+    for assignment in by_start {
+        while let Some(&Reverse((end, _))) = open.peek() {
+            if end >= assignment.sections.start() {
+                break;
+            }
+            open.pop();
+        }
+        for &Reverse((_, line)) in &open {
+            if line != assignment.line {
+                pairs.insert((line.min(assignment.line), line.max(assignment.line)));
+            }
+        }
+        open.push(Reverse((assignment.sections.end(), assignment.line)));
+    }
+    pairs.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::read_lines_panicky;
+
+    fn brute_force_pairs(assignments: &[Assignment]) -> Vec<(usize, usize)> {
+        let mut pairs = BTreeSet::new();
+        for a in assignments {
+            for b in assignments {
+                if a.line < b.line && a.sections.overlaps(b.sections) {
+                    pairs.insert((a.line, b.line));
+                }
+            }
+        }
+        pairs.into_iter().collect()
+    }
+
+    #[test]
+    fn test_coverage_sample() {
+        let coverage = coverage(&assignments(read_lines_panicky("test_input.txt")));
+        assert_eq!(Some(Interval::new(2, 9)), coverage.span);
+        assert_eq!(8, coverage.max_elves);
+        assert_eq!("6-6", coverage.busiest.to_string());
+        assert!(coverage.uncovered.is_empty());
+    }
+
+    #[test]
+    fn test_uncovered() {
+        let coverage = coverage(&assignments(["1-2,8-9", "4-4,9-12"]));
+        assert_eq!(Some(Interval::new(1, 12)), coverage.span);
+        assert_eq!("3-3,5-7", coverage.uncovered.to_string());
+        assert_eq!(2, coverage.max_elves);
+        assert_eq!("9-9", coverage.busiest.to_string());
+    }
+
+    #[test]
+    fn test_extremes() {
+        let all = format!("{}-{}", i64::MIN, i64::MAX);
+        let top = format!("{}-{}", i64::MAX - 1, i64::MAX);
+        let coverage = coverage(&assignments([format!("{},{}", all, top)]));
+        assert_eq!(Some(Interval::new(i64::MIN, i64::MAX)), coverage.span);
+        assert_eq!(2, coverage.max_elves);
+        assert_eq!(top, coverage.busiest.to_string());
+        assert!(coverage.uncovered.is_empty());
+    }
+
+    #[test]
+    fn test_empty() {
+        let coverage = coverage(&[]);
+        assert_eq!(None, coverage.span);
+        assert_eq!(0, coverage.max_elves);
+        assert!(overlapping_lines(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_overlapping_lines_sample() {
+        let assignments = assignments(read_lines_panicky("test_input.txt"));
+        let pairs = overlapping_lines(&assignments);
+        assert_eq!(brute_force_pairs(&assignments), pairs);
+        assert!(pairs.contains(&(1, 3)) && !pairs.contains(&(1, 1)));
+    }
+
+    #[test]
+    fn test_overlapping_lines_input() {
+        let assignments = assignments(read_lines_panicky("input.txt"));
+        assert_eq!(
+            brute_force_pairs(&assignments),
+            overlapping_lines(&assignments)
+        );
+    }
+
+    #[test]
+    fn test_coverage_matches_brute_force() {
+        let assignments = assignments(read_lines_panicky("input.txt"));
+        let coverage = coverage(&assignments);
+        let span = coverage.span.unwrap();
+        let counts: Vec<usize> = (span.start()..=span.end())
+            .map(|s| {
+                assignments
+                    .iter()
+                    .filter(|a| a.sections.contains(s))
+                    .count()
+            })
+            .collect();
+        let max = *counts.iter().max().unwrap();
+        assert_eq!(max, coverage.max_elves);
+        for (s, &count) in (span.start()..).zip(&counts) {
+            assert_eq!(count == max, coverage.busiest.contains(s));
+            assert_eq!(count == 0, coverage.uncovered.contains(s));
+        }
+    }
+}
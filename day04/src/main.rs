@@ -1,11 +1,52 @@
-use helpers::read_lines_panicky;
+use helpers::{read_lines_panicky, Args, Interval, Results};
 
-type Range = (usize, usize);
-type Pair = (Range, Range);
+mod coverage;
+
+type Pair = (Interval, Interval);
 
 fn main() {
-    println!("Part 1: {}", part1("input.txt"));
-    println!("Part 2: {}", part2("input.txt"));
+    let args = Args::parse_with(env!("CARGO_MANIFEST_DIR"), &["coverage", "pairs"]);
+    if args.flag("coverage") {
+        print_coverage(&args);
+        return;
+    }
+
+    let mut results = Results::new(4, &args);
+    results.part(1, || part1(&args.input));
+    results.part(2, || part2(&args.input));
+    results.print();
+}
+
+// Treats every assignment in the file as one population. `--pairs` also lists the lines
+// whose assignments overlap, not just how many there are.
+fn print_coverage(args: &Args) {
+    let assignments = coverage::assignments(read_lines_panicky(&args.input));
+    let coverage = coverage::coverage(&assignments);
+    let pairs = coverage::overlapping_lines(&assignments);
+
+    println!("{} assignments", assignments.len());
+    if let Some(span) = coverage.span {
+        println!("Sections {} ({} sections)", span, span.len());
+    }
+    println!(
+        "Most elves on one section: {} ({})",
+        coverage.max_elves, coverage.busiest
+    );
+    if coverage.uncovered.is_empty() {
+        println!("Sections covered by nobody: 0");
+    } else {
+        println!(
+            "Sections covered by nobody: {} ({})",
+            coverage.uncovered.len(),
+            coverage.uncovered
+        );
+    }
+    println!("Pairs of lines that overlap: {}", pairs.len());
+    if args.flag("pairs") {
+        for (first, second) in pairs {
+            println!("  {} {}", first, second);
+        }
+    }
 }
 
 fn part1(path: &str) -> usize {
@@ -18,38 +59,17 @@ fn part1(path: &str) -> usize {
 fn part2(path: &str) -> usize {
     read_lines_panicky(path)
         .map(|line| parse_pair(&line))
-        .filter(|&pair| is_overlapping(pair))
+        .filter(|&(first, second)| first.overlaps(second))
         .count()
 }
 
 fn parse_pair(line: &str) -> Pair {
-    let mut split = line.split(",");
-    let first = split.next().unwrap();
-    let second = split.next().unwrap();
-    (parse_range(first), parse_range(second))
-}
-
-fn parse_range(input: &str) -> Range {
-    let mut split = input.split("-");
-    let start = split.next().unwrap();
-    let end = split.next().unwrap();
-    (start.parse().unwrap(), end.parse().unwrap())
+    let (first, second) = line.split_once(',').unwrap();
+    (first.parse().unwrap(), second.parse().unwrap())
 }
 
 fn is_fully_contained((first, second): Pair) -> bool {
-    (is_in_range(first.0, second) && is_in_range(first.1, second))
-        || (is_in_range(second.0, first) && is_in_range(second.1, first))
-}
-
-fn is_overlapping((first, second): Pair) -> bool {
-    is_in_range(first.0, second)
-        || is_in_range(first.1, second)
-        || is_in_range(second.0, first)
-        || is_in_range(second.1, first)
-}
-
-fn is_in_range(point: usize, range: Range) -> bool {
-    range.0 <= point && point <= range.1
+    first.contains_interval(second) || second.contains_interval(first)
 }
 
 #[cfg(test)]
@@ -0,0 +1,240 @@
+use crate::Step;
+
+/// A model of crane, deciding what order a step's crates land in on their new stack.
+pub trait Crane {
+    /// Puts down `lifted`, given bottom crate first as they sat on their old stack.
+    fn put_down(&self, lifted: &[char], target: &mut Vec<char>);
+
+    /// Moves a step's crates with one bulk copy rather than popping and pushing them one at a
+    /// time, which is what makes steps of millions of crates cheap.
+    fn apply(&self, stacks: &mut [Vec<char>], &Step { count, from, to }: &Step) {
+        // Every model would put the crates straight back where they came from.
+        if from == to {
+            return;
+        }
+        let (source, target) = source_and_target(stacks, from, to);
+        let at = source.len() - count;
+        self.put_down(&source[at..], target);
+        source.truncate(at);
+    }
+}
+
+fn source_and_target(
+    stacks: &mut [Vec<char>],
+    from: usize,
+    to: usize,
+) -> (&mut Vec<char>, &mut Vec<char>) {
+    if from < to {
+        let (left, right) = stacks.split_at_mut(to);
+        (&mut left[from], &mut right[0])
+    } else {
+        let (left, right) = stacks.split_at_mut(from);
+        (&mut right[0], &mut left[to])
+    }
+}
+
+/// Moves crates one at a time, so they land in reverse order.
+pub struct CrateMover9000;
+
+/// Moves all of a step's crates at once, keeping their order.
+pub struct CrateMover9001;
+
+/// Moves up to `capacity` crates at a time. Each lift keeps its crates in order, and later
+/// lifts land on top of earlier ones.
+pub struct Batched {
+    pub capacity: usize,
+}
+
+impl Crane for CrateMover9000 {
+    fn put_down(&self, lifted: &[char], target: &mut Vec<char>) {
+        let at = target.len();
+        target.extend_from_slice(lifted);
+        target[at..].reverse();
+    }
+}
+
+impl Crane for CrateMover9001 {
+    fn put_down(&self, lifted: &[char], target: &mut Vec<char>) {
+        target.extend_from_slice(lifted);
+    }
+}
+
+impl Crane for Batched {
+    fn put_down(&self, lifted: &[char], target: &mut Vec<char>) {
+        assert!(self.capacity > 0, "Crane capacity must be positive");
+        for lift in lifted.rchunks(self.capacity) {
+            target.extend_from_slice(lift);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::{execute, stacks::Stacks};
+
+    fn stacks() -> Vec<Vec<char>> {
+        vec![vec!['A', 'B', 'C', 'D', 'E'], vec![]]
+    }
+
+    fn move_all(crane: &dyn Crane) -> Vec<char> {
+        let mut stacks = stacks();
+        crane.apply(
+            &mut stacks,
+            &Step {
+                count: 5,
+                from: 0,
+                to: 1,
+            },
+        );
+        assert!(stacks[0].is_empty());
+        stacks.swap_remove(1)
+    }
+
+    #[test]
+    fn test_models() {
+        assert_eq!(vec!['E', 'D', 'C', 'B', 'A'], move_all(&CrateMover9000));
+        assert_eq!(vec!['A', 'B', 'C', 'D', 'E'], move_all(&CrateMover9001));
+        assert_eq!(
+            vec!['D', 'E', 'B', 'C', 'A'],
+            move_all(&Batched { capacity: 2 })
+        );
+    }
+
+    #[test]
+    fn test_move_to_same_stack() {
+        for crane in [
+            &CrateMover9000 as &dyn Crane,
+            &CrateMover9001,
+            &Batched { capacity: 2 },
+        ] {
+            let mut stacks = stacks();
+            crane.apply(
+                &mut stacks,
+                &Step {
+                    count: 3,
+                    from: 0,
+                    to: 0,
+                },
+            );
+            assert_eq!(self::stacks(), stacks);
+        }
+    }
+
+    #[test]
+    fn test_batched_matches_other_models() {
+        assert_eq!(
+            move_all(&CrateMover9000),
+            move_all(&Batched { capacity: 1 })
+        );
+        for capacity in 5..8 {
+            assert_eq!(move_all(&CrateMover9001), move_all(&Batched { capacity }));
+        }
+    }
+
+    /// The original one-crate-at-a-time procedures, for checking and timing against.
+    fn pop_one_at_a_time(stacks: &mut [Vec<char>], steps: &[Step], keep_order: bool) {
+        let mut temp = Vec::new();
+        for &Step { count, from, to } in steps {
+            if !keep_order {
+                for _ in 0..count {
+                    let c = stacks[from].pop().unwrap();
+                    stacks[to].push(c);
+                }
+                continue;
+            }
+
+            for _ in 0..count {
+                let c = stacks[from].pop().unwrap();
+                temp.push(c);
+            }
+            while let Some(c) = temp.pop() {
+                stacks[to].push(c);
+            }
+        }
+    }
+
+    /// Nine stacks of `crates` crates between them, and `steps` random moves of up to
+    /// `largest_move` crates.
+    fn generated(crates: usize, steps: usize, largest_move: usize) -> (Stacks, Vec<Step>) {
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut stacks: Vec<Vec<char>> = (0..9)
+            .map(|_| (0..crates / 9).map(|_| rng.gen_range('A'..='Z')).collect())
+            .collect();
+        let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+        let steps = (0..steps)
+            .map(|_| {
+                let from = rng.gen_range(0..9);
+                let to = (from + rng.gen_range(1..9)) % 9;
+                let count = rng.gen_range(0..=heights[from].min(largest_move));
+                heights[from] -= count;
+                heights[to] += count;
+                Step { count, from, to }
+            })
+            .collect();
+        stacks.shrink_to_fit();
+        (Stacks(stacks), steps)
+    }
+
+    #[test]
+    fn test_bulk_moves_match_popping() {
+        let (initial, steps) = generated(1_000_000, 2000, 1000);
+        for (crane, keep_order) in [
+            (&CrateMover9000 as &dyn Crane, false),
+            (&CrateMover9001, true),
+        ] {
+            let mut expected = initial.clone();
+            pop_one_at_a_time(&mut expected, &steps, keep_order);
+            let mut stacks = initial.clone();
+            execute(&mut stacks, &steps, crane);
+            assert_eq!(expected, stacks);
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_execute() {
+        // Many small moves, and then a few moving millions of crates at once.
+        for (crates, steps, largest_move) in
+            [(1_000_000, 1_000_000, 1000), (18_000_000, 200, usize::MAX)]
+        {
+            let (initial, steps) = generated(crates, steps, largest_move);
+            let moved: usize = steps.iter().map(|s| s.count).sum();
+            let largest = steps.iter().map(|s| s.count).max().unwrap();
+            println!(
+                "{} steps moving {} crates, at most {} at once",
+                steps.len(),
+                moved,
+                largest
+            );
+
+            for (name, crane, keep_order) in [
+                ("CrateMover 9000", &CrateMover9000 as &dyn Crane, false),
+                ("CrateMover 9001", &CrateMover9001, true),
+            ] {
+                let mut expected = initial.clone();
+                let now = Instant::now();
+                pop_one_at_a_time(&mut expected, &steps, keep_order);
+                let popping = now.elapsed();
+
+                let mut stacks = initial.clone();
+                let now = Instant::now();
+                execute(&mut stacks, &steps, crane);
+                let bulk = now.elapsed();
+
+                assert_eq!(expected, stacks);
+                println!(
+                    "{}: one at a time {:.2?}, execute {:.2?} ({:.1}x)",
+                    name,
+                    popping,
+                    bulk,
+                    popping.as_secs_f64() / bulk.as_secs_f64()
+                );
+            }
+        }
+    }
+}
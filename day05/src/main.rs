@@ -1,117 +1,146 @@
-use helpers::read_lines_panicky;
+use std::num::NonZeroUsize;
+
+use crane::{Batched, Crane, CrateMover9000, CrateMover9001};
+use helpers::{read_lines_panicky, Args, Results};
 use lazy_static::lazy_static;
 use regex::Regex;
+use simulator::{Simulator, StepError};
+use stacks::Stacks;
+
+mod crane;
+mod simulator;
+mod stacks;
 
 fn main() {
-    println!("Part 1: {}", part1("input.txt"));
-    println!("Part 2: {}", part2("input.txt"));
+    let args = Args::parse_with(env!("CARGO_MANIFEST_DIR"), &["show", "after=", "batch="]);
+    check_steps(&args.input);
+
+    if args.flag("show") {
+        show(&args.input, args.parsed_option("after"));
+        return;
+    }
+    // A capacity of 0 is rejected like any other malformed value, rather than by the crane.
+    if let Some(capacity) = args.parsed_option::<NonZeroUsize>("batch") {
+        let crane = Batched {
+            capacity: capacity.get(),
+        };
+        println!("{}", run(&args.input, &crane));
+        return;
+    }
+
+    let mut results = Results::new(5, &args);
+    results.part(1, || part1(&args.input));
+    results.part(2, || part2(&args.input));
+    results.print();
 }
 
 fn part1(path: &str) -> String {
-    let mut stacks = parse_stacks(path);
+    run(path, &CrateMover9000)
+}
 
-    for Step { count, from, to } in parse_steps(path) {
-        for _ in 0..count {
-            let c = stacks[from].pop().unwrap();
-            stacks[to].push(c);
-        }
-    }
+fn part2(path: &str) -> String {
+    run(path, &CrateMover9001)
+}
 
-    top_crates(&stacks)
+fn simulator<'a>(path: &str, crane: &'a dyn Crane) -> Simulator<'a> {
+    Simulator::new(crane, parse_stacks(path), parse_steps(path).unwrap())
 }
 
-fn part2(path: &str) -> String {
+fn run(path: &str, crane: &dyn Crane) -> String {
     let mut stacks = parse_stacks(path);
-    let mut temp = Vec::new();
+    let steps = parse_steps(path).unwrap_or_else(|e| panic!("{}", e));
+    execute(&mut stacks, &steps, crane);
+    top_crates(&stacks)
+}
 
-    for Step { count, from, to } in parse_steps(path) {
-        for _ in 0..count {
-            let c = stacks[from].pop().unwrap();
-            temp.push(c);
-        }
+/// Carries out every step with the crane directly. Unlike the `Simulator`, nothing is kept
+/// for undoing steps, so the only work per step is the crane's own bulk move.
+fn execute(stacks: &mut Stacks, steps: &[Step], crane: &dyn Crane) {
+    simulator::check_all(stacks, steps).unwrap_or_else(|e| panic!("{}", e));
+    for step in steps {
+        crane.apply(stacks, step);
+    }
+}
 
-        while let Some(c) = temp.pop() {
-            stacks[to].push(c);
-        }
+// Checking the steps only needs the stacks' heights, so bad steps are caught up front without
+// moving any crates.
+fn check_steps(path: &str) {
+    let checked =
+        parse_steps(path).and_then(|steps| simulator::check_all(&parse_stacks(path), &steps));
+    if let Err(e) = checked {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
     }
+}
 
-    top_crates(&stacks)
+/// Prints the starting diagram and each crane's diagram after `after` steps, or at the end.
+fn show(path: &str, after: Option<usize>) {
+    println!("{}\n", parse_stacks(path));
+    for (name, crane) in [
+        ("CrateMover 9000", &CrateMover9000 as &dyn Crane),
+        ("CrateMover 9001", &CrateMover9001),
+    ] {
+        let mut simulator = simulator(path, crane);
+        let position = after.unwrap_or(simulator.len()).min(simulator.len());
+        simulator.seek(position).unwrap();
+        println!(
+            "After {} steps with the {}:\n{}\n",
+            position,
+            name,
+            simulator.stacks()
+        );
+    }
 }
 
+/// The crate on top of each stack, with a space for a stack left empty.
 fn top_crates(stacks: &[Vec<char>]) -> String {
-    stacks.iter().map(|s| s.last().unwrap()).collect()
+    stacks
+        .iter()
+        .map(|s| s.last().copied().unwrap_or(' '))
+        .collect()
 }
 
-fn parse_stacks(path: &str) -> Vec<Vec<char>> {
-    let mut lines: Vec<String> = read_lines_panicky(path)
+fn parse_stacks(path: &str) -> Stacks {
+    let diagram: Vec<String> = read_lines_panicky(path)
         .take_while(|l| !l.starts_with("move"))
-        .filter(|l| !l.is_empty())
         .collect();
-
-    let labels = lines.pop().unwrap();
-    let num_stacks = labels.chars().filter(|c| c.is_numeric()).count();
-
-    let mut stacks = Vec::with_capacity(num_stacks);
-    stacks.resize(num_stacks, Vec::new());
-
-    for line in lines.into_iter().rev() {
-        let bytes = line.as_bytes();
-        for (i, stack) in stacks.iter_mut().enumerate() {
-            let idx = (i * 4) + 1;
-            let c = bytes[idx];
-            if c != b' ' {
-                stack.push(c as char);
-            }
-        }
-    }
-
-    stacks
+    diagram.join("\n").parse().unwrap()
 }
 
-// fn print(stacks: &[Vec<char>]) {
-//     let max_height = stacks.into_iter().map(|s| s.len()).max().unwrap();
-//     for i in (0..max_height).rev() {
-//         use std::fmt::Write;
-//         let mut line = String::new();
-//         for stack in stacks {
-//             if stack.len() > i {
-//                 write!(&mut line, "[{}] ", stack[i]).unwrap();
-//             } else {
-//                 write!(&mut line, "    ").unwrap();
-//             }
-//         }
-//         println!("{}", line)
-//     }
-//     for i in 1..=stacks.len() {
-//         print!(" {}  ", i);
-//     }
-//     println!();
-// }
-
-fn parse_steps(path: &str) -> impl Iterator<Item = Step> + '_ {
+fn parse_steps(path: &str) -> Result<Vec<Step>, StepError> {
     read_lines_panicky(path)
         .filter(|l| l.starts_with("move"))
-        .map(|l| parse_step(&l))
+        .enumerate()
+        .map(|(i, l)| parse_step(i, &l))
+        .collect()
 }
 
-#[derive(PartialEq, Eq, Debug)]
-struct Step {
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Step {
     from: usize,
     to: usize,
     count: usize,
 }
 
-fn parse_step(line: &str) -> Step {
+/// Parses one step. `index` is its 0-based position among the steps, for reporting errors.
+fn parse_step(index: usize, line: &str) -> Result<Step, StepError> {
     lazy_static! {
         static ref RE: Regex = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
     }
 
     let caps = RE.captures(line).unwrap();
     let count = caps[1].parse::<usize>().unwrap();
-    let from = caps[2].parse::<usize>().unwrap() - 1;
-    let to = caps[3].parse::<usize>().unwrap() - 1;
-
-    Step { from, to, count }
+    let stack = |i: usize| {
+        caps[i]
+            .parse::<usize>()
+            .unwrap()
+            .checked_sub(1)
+            .ok_or(StepError::StackZero { step: index + 1 })
+    };
+    let from = stack(2)?;
+    let to = stack(3)?;
+
+    Ok(Step { from, to, count })
 }
 
 #[cfg(test)]
@@ -123,9 +152,24 @@ mod tests {
         assert_eq!("CMZ", &part1("test_input.txt"));
     }
 
+    #[test]
+    fn test_top_crates_of_empty_stack() {
+        let mut stacks: Stacks = "    [A]\n[B] [C]\n 1   2 ".parse().unwrap();
+        execute(
+            &mut stacks,
+            &[Step {
+                count: 2,
+                from: 1,
+                to: 0,
+            }],
+            &CrateMover9000,
+        );
+        assert_eq!("C ", top_crates(&stacks));
+    }
+
     #[test]
     fn test_parse_stacks() {
-        let expected = vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
+        let expected = Stacks(vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]);
         assert_eq!(expected, parse_stacks("test_input.txt"));
     }
 
@@ -133,15 +177,26 @@ mod tests {
     fn test_parse_step() {
         // 'from' and 'to' are intentionally one less, for indexing position
         assert_eq!(
-            Step {
+            Ok(Step {
                 count: 13,
                 from: 6,
                 to: 7
-            },
-            parse_step("move 13 from 7 to 8")
+            }),
+            parse_step(0, "move 13 from 7 to 8")
         );
     }
 
+    #[test]
+    fn test_parse_step_zero() {
+        let error = parse_step(4, "move 1 from 0 to 2").unwrap_err();
+        assert_eq!(StepError::StackZero { step: 5 }, error);
+        assert_eq!(
+            "step 5: there is no stack 0, stacks are numbered from 1",
+            error.to_string()
+        );
+        assert!(parse_step(0, "move 1 from 2 to 0").is_err());
+    }
+
     #[test]
     fn part1_final() {
         assert_eq!("SHMSDGZVC", &part1("input.txt"));
@@ -156,4 +211,17 @@ mod tests {
     fn part2_final() {
         assert_eq!("VRZGHDFBQ", &part2("input.txt"));
     }
+
+    #[test]
+    fn batched_sample() {
+        assert_eq!("CMZ", &run("test_input.txt", &Batched { capacity: 1 }));
+        assert_eq!("MCD", &run("test_input.txt", &Batched { capacity: 3 }));
+        assert_eq!("MCZ", &run("test_input.txt", &Batched { capacity: 2 }));
+    }
+
+    #[test]
+    fn batched_final() {
+        assert_eq!("SHMSDGZVC", &run("input.txt", &Batched { capacity: 1 }));
+        assert_eq!("VRZGHDFBQ", &run("input.txt", &Batched { capacity: 99 }));
+    }
 }
@@ -0,0 +1,295 @@
+use std::fmt;
+
+use crate::{crane::Crane, stacks::Stacks, Step};
+
+/// Why a step can't be carried out. Steps and stacks are numbered from 1, as in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepError {
+    StackZero {
+        step: usize,
+    },
+    NoSuchStack {
+        step: usize,
+        stack: usize,
+        stacks: usize,
+    },
+    NotEnoughCrates {
+        step: usize,
+        stack: usize,
+        requested: usize,
+        available: usize,
+    },
+}
+
+impl fmt::Display for StepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StepError::StackZero { step } => write!(
+                f,
+                "step {}: there is no stack 0, stacks are numbered from 1",
+                step
+            ),
+            StepError::NoSuchStack {
+                step,
+                stack,
+                stacks: 0,
+            } => write!(
+                f,
+                "step {}: there is no stack {}, there are no stacks",
+                step, stack
+            ),
+            StepError::NoSuchStack {
+                step,
+                stack,
+                stacks,
+            } => write!(
+                f,
+                "step {}: there is no stack {}, only stacks 1 to {}",
+                step, stack, stacks
+            ),
+            StepError::NotEnoughCrates {
+                step,
+                stack,
+                requested,
+                available,
+            } => write!(
+                f,
+                "step {}: can't move {} crates from stack {}, it only has {}",
+                step, requested, stack, available
+            ),
+        }
+    }
+}
+
+fn check_step(index: usize, step: &Step, heights: &[usize]) -> Result<(), StepError> {
+    for stack in [step.from, step.to] {
+        if stack >= heights.len() {
+            return Err(StepError::NoSuchStack {
+                step: index + 1,
+                stack: stack + 1,
+                stacks: heights.len(),
+            });
+        }
+    }
+    if heights[step.from] < step.count {
+        return Err(StepError::NotEnoughCrates {
+            step: index + 1,
+            stack: step.from + 1,
+            requested: step.count,
+            available: heights[step.from],
+        });
+    }
+    Ok(())
+}
+
+/// Checks every step against the stacks, without moving any crates: whether a step can be
+/// carried out only depends on how many crates each stack has, whatever the crane.
+pub fn check_all(stacks: &Stacks, steps: &[Step]) -> Result<(), StepError> {
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    for (index, step) in steps.iter().enumerate() {
+        check_step(index, step, &heights)?;
+        heights[step.from] -= step.count;
+        heights[step.to] += step.count;
+    }
+    Ok(())
+}
+
+/// Runs steps through a crane one at a time, checking each before it's applied. Every applied
+/// step can be undone, and undone steps redone, so any point in the procedure can be revisited.
+pub struct Simulator<'a> {
+    crane: &'a dyn Crane,
+    stacks: Stacks,
+    steps: Vec<Step>,
+    /// The crates each applied step took off its source stack, bottom first, so it can be
+    /// undone whatever order the crane put them down in.
+    lifted: Vec<Vec<char>>,
+}
+
+impl<'a> Simulator<'a> {
+    pub fn new(crane: &'a dyn Crane, stacks: Stacks, steps: Vec<Step>) -> Self {
+        Self {
+            crane,
+            stacks,
+            steps,
+            lifted: Vec::new(),
+        }
+    }
+
+    pub fn stacks(&self) -> &Stacks {
+        &self.stacks
+    }
+
+    /// How many steps have been applied.
+    pub fn position(&self) -> usize {
+        self.lifted.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn check(&self, index: usize) -> Result<(), StepError> {
+        let heights: Vec<usize> = self.stacks.iter().map(Vec::len).collect();
+        check_step(index, &self.steps[index], &heights)
+    }
+
+    /// Applies the next step, returning `Ok(false)` if there are none left.
+    pub fn redo(&mut self) -> Result<bool, StepError> {
+        let index = self.position();
+        if index == self.steps.len() {
+            return Ok(false);
+        }
+        self.check(index)?;
+
+        let step = &self.steps[index];
+        let from = &self.stacks[step.from];
+        self.lifted.push(from[from.len() - step.count..].to_vec());
+        self.crane.apply(&mut self.stacks, step);
+        Ok(true)
+    }
+
+    /// Undoes the last applied step, returning `false` if there are none.
+    pub fn undo(&mut self) -> bool {
+        let Some(lifted) = self.lifted.pop() else {
+            return false;
+        };
+        let step = &self.steps[self.lifted.len()];
+        let to = &mut self.stacks[step.to];
+        to.truncate(to.len() - step.count);
+        self.stacks[step.from].extend(lifted);
+        true
+    }
+
+    /// Moves to the state after `position` steps, stopping early at a step that can't be
+    /// applied.
+    pub fn seek(&mut self, position: usize) -> Result<(), StepError> {
+        assert!(
+            position <= self.steps.len(),
+            "There are only {} steps",
+            self.steps.len()
+        );
+        while self.position() > position {
+            self.undo();
+        }
+        while self.position() < position {
+            self.redo()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::{CrateMover9000, CrateMover9001};
+    use crate::{parse_stacks, parse_steps};
+
+    fn sample(crane: &dyn Crane) -> Simulator<'_> {
+        Simulator::new(
+            crane,
+            parse_stacks("test_input.txt"),
+            parse_steps("test_input.txt").unwrap(),
+        )
+    }
+
+    fn step(count: usize, from: usize, to: usize) -> Step {
+        Step { count, from, to }
+    }
+
+    #[test]
+    fn test_undo_and_redo() {
+        let mut simulator = sample(&CrateMover9000);
+        let initial = simulator.stacks().clone();
+        let mut states = vec![initial.clone()];
+        while simulator.redo().unwrap() {
+            states.push(simulator.stacks().clone());
+        }
+        assert_eq!(4, simulator.position());
+        assert_eq!(
+            "[C] [M] [P]",
+            simulator.stacks().to_string().lines().nth(3).unwrap()
+        );
+
+        while simulator.undo() {
+            assert_eq!(&states[simulator.position()], simulator.stacks());
+        }
+        assert_eq!(&initial, simulator.stacks());
+        assert!(!simulator.undo());
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut simulator = sample(&CrateMover9001);
+        simulator.seek(2).unwrap();
+        let after_two = simulator.stacks().clone();
+        assert_eq!(
+            Stacks(vec![vec![], vec!['M', 'C'], vec!['P', 'Z', 'N', 'D']]),
+            after_two
+        );
+        simulator.seek(simulator.len()).unwrap();
+        simulator.seek(2).unwrap();
+        assert_eq!(&after_two, simulator.stacks());
+        simulator.seek(0).unwrap();
+        assert_eq!(&parse_stacks("test_input.txt"), simulator.stacks());
+    }
+
+    #[test]
+    fn test_not_enough_crates() {
+        let stacks = Stacks(vec![vec!['A'], vec![]]);
+        let steps = vec![step(1, 0, 1), step(2, 1, 0)];
+        let mut simulator = Simulator::new(&CrateMover9000, stacks, steps);
+        let error = simulator.seek(simulator.len()).unwrap_err();
+        assert_eq!(
+            StepError::NotEnoughCrates {
+                step: 2,
+                stack: 2,
+                requested: 2,
+                available: 1
+            },
+            error
+        );
+        assert_eq!(
+            "step 2: can't move 2 crates from stack 2, it only has 1",
+            error.to_string()
+        );
+        // The failed step isn't applied, and the ones before it stay applied.
+        assert_eq!(1, simulator.position());
+        assert_eq!(&Stacks(vec![vec![], vec!['A']]), simulator.stacks());
+    }
+
+    #[test]
+    fn test_check_all() {
+        let stacks = Stacks(vec![vec!['A'], vec![]]);
+        assert_eq!(Ok(()), check_all(&stacks, &[step(1, 0, 1), step(1, 1, 0)]));
+        let steps = [step(1, 0, 1), step(2, 1, 0)];
+        let mut simulator = Simulator::new(&CrateMover9000, stacks.clone(), steps.to_vec());
+        assert_eq!(simulator.seek(simulator.len()), check_all(&stacks, &steps));
+    }
+
+    #[test]
+    fn test_no_such_stack() {
+        let stacks = Stacks(vec![vec!['A'], vec![]]);
+        let mut simulator = Simulator::new(&CrateMover9000, stacks, vec![step(1, 0, 4)]);
+        assert_eq!(
+            "step 1: there is no stack 5, only stacks 1 to 2",
+            simulator.redo().unwrap_err().to_string()
+        );
+        assert_eq!(0, simulator.position());
+        assert_eq!(
+            "step 1: there is no stack 1, there are no stacks",
+            check_all(&Stacks::default(), &[step(1, 0, 0)])
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_undo_move_onto_same_stack() {
+        let stacks = Stacks(vec![vec!['A', 'B', 'C']]);
+        let mut simulator = Simulator::new(&CrateMover9000, stacks.clone(), vec![step(2, 0, 0)]);
+        simulator.seek(simulator.len()).unwrap();
+        assert_eq!(&stacks, simulator.stacks());
+        assert!(simulator.undo());
+        assert_eq!(&stacks, simulator.stacks());
+    }
+}
@@ -0,0 +1,232 @@
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
+/// The stacks of crates, bottom crate first, in the puzzle's diagram format:
+///
+/// ```text
+///     [D]
+/// [N] [C]
+/// [Z] [M] [P]
+///  1   2   3
+/// ```
+///
+/// Columns are three characters wide and separated by a space, widening only once the labels
+/// need more than three digits. Labels are centered, with any spare space on the right.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Stacks(pub Vec<Vec<char>>);
+
+impl Deref for Stacks {
+    type Target = Vec<Vec<char>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Stacks {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+fn column_width(stacks: usize) -> usize {
+    stacks.to_string().len().max(3)
+}
+
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = column_width(self.len());
+        let height = self.iter().map(Vec::len).max().unwrap_or(0);
+        for row in (0..height).rev() {
+            let cells: Vec<String> = self
+                .iter()
+                .map(|stack| match stack.get(row) {
+                    Some(c) => format!("{:<width$}", format!("[{}]", c)),
+                    None => " ".repeat(width),
+                })
+                .collect();
+            writeln!(f, "{}", cells.join(" "))?;
+        }
+
+        let labels: Vec<String> = (1..=self.len())
+            .map(|label| format!("{:^width$}", label))
+            .collect();
+        write!(f, "{}", labels.join(" "))
+    }
+}
+
+/// Parses a diagram written by `Display`. Trailing whitespace may have been stripped from
+/// any line, and blank lines around the diagram are ignored, so a blank diagram has no stacks.
+/// Rows in errors are counted up from the bottom.
+impl FromStr for Stacks {
+    type Err = String;
+
+    fn from_str(diagram: &str) -> Result<Self, String> {
+        let mut lines: Vec<&str> = diagram.lines().collect();
+        while lines.last().is_some_and(|l| l.trim().is_empty()) {
+            lines.pop();
+        }
+        let first = lines.iter().take_while(|l| l.trim().is_empty()).count();
+        // No stacks at all display as nothing.
+        let Some(labels) = lines.pop() else {
+            return Ok(Stacks::default());
+        };
+
+        let count = labels.split_whitespace().count();
+        for (i, label) in labels.split_whitespace().enumerate() {
+            if label != (i + 1).to_string() {
+                return Err(format!("expected stack label {}, found `{}`", i + 1, label));
+            }
+        }
+
+        let width = column_width(count);
+        let mut stacks = vec![Vec::new(); count];
+        for (row, line) in lines[first..].iter().rev().enumerate() {
+            let line: Vec<char> = line.trim_end().chars().collect();
+            if line.len() > count * (width + 1) - 1 {
+                return Err(format!("row {} is wider than {} stacks", row + 1, count));
+            }
+
+            for (i, stack) in stacks.iter_mut().enumerate() {
+                let start = (i * (width + 1)).min(line.len());
+                let end = (start + width).min(line.len());
+                let mut cell = &line[start..end];
+                while let [rest @ .., ' '] = cell {
+                    cell = rest;
+                }
+                let c = match cell {
+                    [] => continue,
+                    ['[', c, ']'] => *c,
+                    _ => {
+                        return Err(format!(
+                            "row {}, stack {}: `{}` is not a crate",
+                            row + 1,
+                            i + 1,
+                            cell.iter().collect::<String>()
+                        ))
+                    }
+                };
+                if stack.len() != row {
+                    return Err(format!(
+                        "row {}, stack {}: crate [{}] is floating in mid-air",
+                        row + 1,
+                        i + 1,
+                        c
+                    ));
+                }
+                stack.push(c);
+            }
+        }
+
+        Ok(Stacks(stacks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const SAMPLE: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ";
+
+    fn sample() -> Stacks {
+        Stacks(vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']])
+    }
+
+    #[test]
+    fn test_render_sample() {
+        assert_eq!(SAMPLE, sample().to_string());
+    }
+
+    #[test]
+    fn test_parse_sample() {
+        assert_eq!(Ok(sample()), SAMPLE.parse());
+        let stripped: Vec<_> = SAMPLE.lines().map(str::trim_end).collect();
+        assert_eq!(
+            Ok(sample()),
+            format!("\n{}\n\n", stripped.join("\n")).parse()
+        );
+    }
+
+    #[test]
+    fn test_multi_digit_labels() {
+        let mut stacks = Stacks(vec![Vec::new(); 12]);
+        stacks[9].push('X');
+        stacks[11].extend(['Y', 'Z']);
+        let diagram = stacks.to_string();
+        let expected = format!(
+            "{}[Z]\n{}[X]     [Y]\n 1   2   3   4   5   6   7   8   9  10  11  12 ",
+            " ".repeat(44),
+            " ".repeat(36)
+        );
+        assert_eq!(expected, diagram);
+        assert_eq!(Ok(stacks), diagram.parse());
+    }
+
+    #[test]
+    fn test_wide_columns() {
+        let mut stacks = Stacks(vec![Vec::new(); 1000]);
+        stacks[999].push('Q');
+        let diagram = stacks.to_string();
+        assert!(diagram.starts_with(&" ".repeat(999 * 5)));
+        assert!(diagram.ends_with(" 998  999  1000"));
+        assert_eq!(Ok(stacks), diagram.parse());
+    }
+
+    #[test]
+    fn test_no_stacks() {
+        assert_eq!("", Stacks::default().to_string());
+        assert_eq!(Ok(Stacks::default()), "".parse());
+        assert_eq!(Ok(Stacks::default()), "\n  \n".parse());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Err("expected stack label 2, found `3`".to_owned()),
+            " 1   3 ".parse::<Stacks>()
+        );
+        assert_eq!(
+            Err("row 1, stack 2: `(B)` is not a crate".to_owned()),
+            "[A] (B)\n 1   2 ".parse::<Stacks>()
+        );
+        assert_eq!(
+            Err("row 2, stack 1: crate [A] is floating in mid-air".to_owned()),
+            "[A]\n    [B]\n 1   2 ".parse::<Stacks>()
+        );
+        assert_eq!(
+            Err("row 1 is wider than 1 stacks".to_owned()),
+            "[A] [B]\n 1 ".parse::<Stacks>()
+        );
+    }
+
+    fn stacks() -> impl Strategy<Value = Stacks> {
+        let stack = prop::collection::vec(prop::char::range('!', '~'), 0..8);
+        prop::collection::vec(stack, 0..120).prop_map(Stacks)
+    }
+
+    proptest! {
+        #[test]
+        fn round_trips(stacks in stacks()) {
+            let diagram = stacks.to_string();
+            prop_assert_eq!(Ok(stacks), diagram.parse::<Stacks>());
+        }
+
+        #[test]
+        fn round_trips_without_trailing_spaces(stacks in stacks()) {
+            let diagram = stacks.to_string();
+            let stripped: Vec<_> = diagram.lines().map(str::trim_end).collect();
+            prop_assert_eq!(Ok(stacks), stripped.join("\n").parse::<Stacks>());
+        }
+
+        #[test]
+        fn render_is_stable(stacks in stacks()) {
+            let diagram = stacks.to_string();
+            let reparsed: Stacks = diagram.parse().unwrap();
+            prop_assert_eq!(diagram, reparsed.to_string());
+        }
+    }
+}
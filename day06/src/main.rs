@@ -1,30 +1,65 @@
-use helpers::read_lines_panicky;
+use std::{fs::File, io, io::Read};
+
+use helpers::{read_lines_panicky, Args, Results, STDIN};
+
+mod marker;
+mod stream;
+
+const START_OF_PACKET: usize = 4;
+const START_OF_MESSAGE: usize = 14;
 
 fn main() {
-    let input = read_lines_panicky("input.txt").next().unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    let args = Args::parse_with(env!("CARGO_MANIFEST_DIR"), &["markers"]);
+    if args.flag("markers") {
+        print_markers(&args.input);
+        return;
+    }
+
+    let input = read_lines_panicky(&args.input).next().unwrap();
+    let mut results = Results::new(6, &args);
+    results.part(1, || part1(&input));
+    results.part(2, || part2(&input));
+    results.print();
 }
 
 fn part1(input: &str) -> usize {
-    find_marker_character(input, 4)
+    find_marker_character(input, START_OF_PACKET)
 }
 
 fn part2(input: &str) -> usize {
-    find_marker_character(input, 14)
+    find_marker_character(input, START_OF_MESSAGE)
 }
 
 fn find_marker_character(input: &str, window_size: usize) -> usize {
-    input
-        .as_bytes()
-        .windows(window_size)
-        .position(|window| !has_duplicates(window)) // thanks AxlLind for this idea
-        .unwrap()
-        + window_size
+    marker::find_marker(input.as_bytes(), window_size).expect("No marker in the signal")
 }
 
-fn has_duplicates(window: &[u8]) -> bool {
-    (1..window.len()).any(|i| window[i..].contains(&window[i - 1]))
+// Streams the signal rather than reading it all in, so it works on signals of any length,
+// including ones still arriving on stdin.
+fn print_markers(path: &str) {
+    let reader: Box<dyn Read> = if path == STDIN {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(File::open(path).unwrap_or_else(|e| panic!("Failed to open {}: {}", path, e)))
+    };
+
+    let mut counts = [0, 0];
+    for marker in stream::markers(reader, &[START_OF_PACKET, START_OF_MESSAGE]) {
+        let marker = marker.unwrap_or_else(|e| {
+            eprintln!("error: failed to read {}: {}", path, e);
+            std::process::exit(1);
+        });
+        let (kind, count) = match marker.window {
+            START_OF_PACKET => ("start-of-packet", &mut counts[0]),
+            _ => ("start-of-message", &mut counts[1]),
+        };
+        *count += 1;
+        println!("{} {}", kind, marker.end);
+    }
+    eprintln!(
+        "{} start-of-packet and {} start-of-message markers",
+        counts[0], counts[1]
+    );
 }
 
 #[cfg(test)]
@@ -0,0 +1,133 @@
+/// Watches a stream of bytes one at a time for windows of `size` distinct bytes. It keeps the
+/// position each byte value was last seen at and the start of the current run of distinct
+/// bytes, so each byte costs O(1) whatever the window size.
+pub struct Detector {
+    size: usize,
+    /// One past the position each byte value was last seen at, or 0 if it hasn't been.
+    last_seen: [usize; 256],
+    run_start: usize,
+    position: usize,
+}
+
+impl Detector {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "Window size must be positive");
+        Self {
+            size,
+            last_seen: [0; 256],
+            run_start: 0,
+            position: 0,
+        }
+    }
+
+    /// Feeds in the next byte, returning whether it completes a window of distinct bytes.
+    pub fn push(&mut self, byte: u8) -> bool {
+        let seen = &mut self.last_seen[byte as usize];
+        self.run_start = self.run_start.max(*seen);
+        self.position += 1;
+        *seen = self.position;
+        self.position - self.run_start >= self.size
+    }
+}
+
+/// The number of bytes up to and including the end of the first window of `size` distinct
+/// bytes, if there is one.
+pub fn find_marker(input: &[u8], size: usize) -> Option<usize> {
+    let mut detector = Detector::new(size);
+    input.iter().position(|&b| detector.push(b)).map(|i| i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    /// The original approach, checking every window pair by pair.
+    fn find_marker_quadratic(input: &[u8], size: usize) -> Option<usize> {
+        input
+            .windows(size)
+            .position(|window| !has_duplicates(window)) // thanks AxlLind for this idea
+            .map(|i| i + size)
+    }
+
+    fn has_duplicates(window: &[u8]) -> bool {
+        (1..window.len()).any(|i| window[i..].contains(&window[i - 1]))
+    }
+
+    /// `len` bytes drawn from only `size - 1` values, so no window has a marker until the
+    /// `size` distinct bytes on the end.
+    fn stream(len: usize, size: usize) -> Vec<u8> {
+        let mut rng = StdRng::seed_from_u64(6);
+        let mut stream: Vec<u8> = (0..len).map(|_| rng.gen_range(0..size - 1) as u8).collect();
+        stream.extend((0..size).map(|b| b as u8));
+        stream
+    }
+
+    #[test]
+    fn test_find_marker() {
+        assert_eq!(Some(7), find_marker(b"mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4));
+        assert_eq!(Some(19), find_marker(b"mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14));
+        assert_eq!(Some(1), find_marker(b"aaa", 1));
+        assert_eq!(None, find_marker(b"abcabc", 4));
+        assert_eq!(None, find_marker(b"", 4));
+    }
+
+    #[test]
+    fn test_large_windows() {
+        for size in [100, 255, 256] {
+            let input = stream(10_000, size);
+            assert_eq!(Some(input.len()), find_marker(&input, size));
+            assert_eq!(
+                find_marker_quadratic(&input, size),
+                find_marker(&input, size)
+            );
+        }
+        assert_eq!(None, find_marker(&stream(10_000, 256), 257));
+    }
+
+    #[test]
+    fn test_matches_quadratic() {
+        let mut rng = StdRng::seed_from_u64(6);
+        for _ in 0..1000 {
+            let size = rng.gen_range(1..20);
+            let input: Vec<u8> = (0..rng.gen_range(0..100))
+                .map(|_| rng.gen_range(b'a'..b'a' + 20))
+                .collect();
+            assert_eq!(
+                find_marker_quadratic(&input, size),
+                find_marker(&input, size),
+                "{:?} {}",
+                input,
+                size
+            );
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_find_marker() {
+        for size in [4, 14, 100] {
+            let input = stream(8 << 20, size);
+
+            let now = Instant::now();
+            let expected = find_marker_quadratic(&input, size);
+            let quadratic = now.elapsed();
+
+            let now = Instant::now();
+            assert_eq!(expected, find_marker(&input, size));
+            let linear = now.elapsed();
+
+            println!(
+                "window {:>3} over {} MiB: pairwise {:.2?}, last seen {:.2?} ({:.1}x)",
+                size,
+                input.len() >> 20,
+                quadratic,
+                linear,
+                quadratic.as_secs_f64() / linear.as_secs_f64()
+            );
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    collections::VecDeque,
+    io::{self, ErrorKind, Read},
+};
+
+use crate::marker::Detector;
+
+const BUFFER_SIZE: usize = 8 << 10;
+
+/// The end of a window of `window` distinct bytes, `end` bytes into the signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Marker {
+    pub window: usize,
+    pub end: usize,
+}
+
+/// Every marker in a signal read from `reader`, for each of the window sizes, in the order
+/// they end. The signal is read a buffer at a time, so it never has to fit in memory, and ends
+/// at the first line ending, `\n` or `\r\n`, like the puzzle's one-line input.
+pub struct Markers<R> {
+    reader: R,
+    buffer: Box<[u8]>,
+    filled: usize,
+    next: usize,
+    offset: usize,
+    detectors: Vec<(usize, Detector)>,
+    pending: VecDeque<Marker>,
+    done: bool,
+}
+
+pub fn markers<R: Read>(reader: R, windows: &[usize]) -> Markers<R> {
+    Markers {
+        reader,
+        buffer: vec![0; BUFFER_SIZE].into_boxed_slice(),
+        filled: 0,
+        next: 0,
+        offset: 0,
+        detectors: windows.iter().map(|&w| (w, Detector::new(w))).collect(),
+        pending: VecDeque::new(),
+        done: false,
+    }
+}
+
+impl<R: Read> Markers<R> {
+    /// The next byte of the signal, reading more of it if the buffer has run out.
+    fn next_byte(&mut self) -> io::Result<Option<u8>> {
+        while self.next == self.filled {
+            match self.reader.read(&mut self.buffer) {
+                Ok(0) => return Ok(None),
+                Ok(n) => (self.filled, self.next) = (n, 0),
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        self.next += 1;
+        Ok(Some(self.buffer[self.next - 1]))
+    }
+}
+
+impl<R: Read> Iterator for Markers<R> {
+    type Item = io::Result<Marker>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() && !self.done {
+            let byte = match self.next_byte() {
+                Ok(Some(b'\n' | b'\r')) | Ok(None) => {
+                    self.done = true;
+                    break;
+                }
+                Ok(Some(byte)) => byte,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            };
+
+            self.offset += 1;
+            for (window, detector) in &mut self.detectors {
+                if detector.push(byte) {
+                    self.pending.push_back(Marker {
+                        window: *window,
+                        end: self.offset,
+                    });
+                }
+            }
+        }
+        self.pending.pop_front().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::marker::find_marker;
+
+    const SAMPLE: &[u8] = b"mjqjpqmgbljsphdztnvjfqwrcgsmlb\n";
+
+    /// Hands out the signal in pieces of the given sizes, in turn, interrupting itself before
+    /// each one.
+    struct Piecewise<'a> {
+        data: &'a [u8],
+        sizes: Vec<usize>,
+        reads: usize,
+    }
+
+    impl Read for Piecewise<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.reads += 1;
+            if self.reads % 2 == 1 {
+                return Err(ErrorKind::Interrupted.into());
+            }
+            let size = self.sizes[self.reads / 2 % self.sizes.len()]
+                .min(buf.len())
+                .min(self.data.len());
+            buf[..size].copy_from_slice(&self.data[..size]);
+            self.data = &self.data[size..];
+            Ok(size)
+        }
+    }
+
+    fn ends(reader: impl Read, window: usize) -> Vec<usize> {
+        markers(reader, &[window]).map(|m| m.unwrap().end).collect()
+    }
+
+    #[test]
+    fn test_every_marker() {
+        assert_eq!((7..=30).collect::<Vec<_>>(), ends(SAMPLE, 4));
+        assert_eq!(vec![19, 25, 26, 27, 28, 29, 30], ends(SAMPLE, 14));
+    }
+
+    #[test]
+    fn test_crlf() {
+        assert_eq!(vec![4], ends(&b"abcd\r\n"[..], 4));
+        assert_eq!(
+            ends(SAMPLE, 14),
+            ends(&b"mjqjpqmgbljsphdztnvjfqwrcgsmlb\r\n"[..], 14)
+        );
+    }
+
+    #[test]
+    fn test_windows_interleave() {
+        let markers: Vec<_> = markers(&b"abcabcd"[..], &[4, 3])
+            .map(|m| m.unwrap())
+            .map(|m| (m.window, m.end))
+            .collect();
+        assert_eq!(
+            vec![(3, 3), (3, 4), (3, 5), (3, 6), (4, 7), (3, 7)],
+            markers
+        );
+    }
+
+    #[test]
+    fn test_piecewise_input() {
+        let input = std::fs::read("input.txt").unwrap();
+        let expected = ends(&input[..], 14);
+        assert_eq!(Some(expected[0]), find_marker(&input, 14));
+        for sizes in [vec![1], vec![3, 1, 7], vec![BUFFER_SIZE * 2]] {
+            let reader = Piecewise {
+                data: &input,
+                sizes,
+                reads: 0,
+            };
+            assert_eq!(expected, ends(reader, 14));
+        }
+    }
+
+    #[test]
+    fn test_larger_than_buffer() {
+        let input: Vec<u8> = (0..BUFFER_SIZE * 5)
+            .map(|i| b'a' + (i % 20) as u8)
+            .collect();
+        let ends = ends(&input[..], 20);
+        assert_eq!(input.len() - 19, ends.len());
+        assert_eq!(Some(&input.len()), ends.last());
+    }
+
+    #[test]
+    fn test_read_error() {
+        struct Broken;
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("disconnected"))
+            }
+        }
+
+        let mut markers = markers(Broken, &[4]);
+        assert_eq!(
+            "disconnected",
+            markers.next().unwrap().unwrap_err().to_string()
+        );
+        assert!(markers.next().is_none());
+    }
+}
@@ -0,0 +1,313 @@
+use std::collections::BTreeMap;
+
+/// Nodes are referred to by their index in the file system, which never changes.
+pub type NodeId = usize;
+
+pub const ROOT: NodeId = 0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Kind {
+    Dir { children: BTreeMap<String, NodeId> },
+    File { size: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub name: String,
+    pub parent: Option<NodeId>,
+    pub kind: Kind,
+}
+
+impl Node {
+    pub fn is_dir(&self) -> bool {
+        matches!(self.kind, Kind::Dir { .. })
+    }
+}
+
+/// A tree of named directories and files, rooted at `/`. Nodes are only ever added, always
+/// after their parent, so every node's index is greater than its parent's.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSystem {
+    nodes: Vec<Node>,
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileSystem {
+    pub fn new() -> Self {
+        let root = Node {
+            name: String::new(),
+            parent: None,
+            kind: Kind::Dir {
+                children: BTreeMap::new(),
+            },
+        };
+        Self { nodes: vec![root] }
+    }
+
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The parent of `id`, or `id` itself for the root, like `cd ..`.
+    pub fn parent(&self, id: NodeId) -> NodeId {
+        self.nodes[id].parent.unwrap_or(id)
+    }
+
+    /// The children of a directory, in name order. Files have none.
+    pub fn children(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let children = match &self.nodes[id].kind {
+            Kind::Dir { children } => Some(children.values().copied()),
+            Kind::File { .. } => None,
+        };
+        children.into_iter().flatten()
+    }
+
+    pub fn child(&self, id: NodeId, name: &str) -> Option<NodeId> {
+        match &self.nodes[id].kind {
+            Kind::Dir { children } => children.get(name).copied(),
+            Kind::File { .. } => None,
+        }
+    }
+
+    /// Adds a directory called `name` to `parent`, returning the one already there if there
+    /// is one.
+    pub fn add_dir(&mut self, parent: NodeId, name: &str) -> NodeId {
+        self.add(
+            parent,
+            name,
+            Kind::Dir {
+                children: BTreeMap::new(),
+            },
+        )
+    }
+
+    /// Adds a file called `name` to `parent`, returning the one already there if there is one.
+    pub fn add_file(&mut self, parent: NodeId, name: &str, size: usize) -> NodeId {
+        self.add(parent, name, Kind::File { size })
+    }
+
+    fn add(&mut self, parent: NodeId, name: &str, kind: Kind) -> NodeId {
+        let id = self.nodes.len();
+        let Kind::Dir { children } = &mut self.nodes[parent].kind else {
+            panic!("{} is not a directory", self.path(parent));
+        };
+        if let Some(&existing) = children.get(name) {
+            return existing;
+        }
+        children.insert(name.to_owned(), id);
+        self.nodes.push(Node {
+            name: name.to_owned(),
+            parent: Some(parent),
+            kind,
+        });
+        id
+    }
+
+    /// The absolute path of a node, like `/a/e`.
+    pub fn path(&self, id: NodeId) -> String {
+        let mut names = Vec::new();
+        let mut id = id;
+        while let Some(parent) = self.nodes[id].parent {
+            names.push(self.nodes[id].name.as_str());
+            id = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    /// The node at an absolute path, if there is one.
+    pub fn lookup(&self, path: &str) -> Option<NodeId> {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(ROOT, |id, name| self.child(id, name))
+    }
+
+    /// Every node under `id`, including `id` itself, parents before their children and
+    /// siblings in name order.
+    pub fn walk(&self, id: NodeId) -> Vec<NodeId> {
+        let mut walk = Vec::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            walk.push(id);
+            let children: Vec<_> = self.children(id).collect();
+            stack.extend(children.into_iter().rev());
+        }
+        walk
+    }
+
+    pub fn dirs(&self) -> impl Iterator<Item = NodeId> + '_ {
+        (0..self.nodes.len()).filter(|&id| self.nodes[id].is_dir())
+    }
+
+    /// Every node, in walk order, that `predicate` accepts.
+    pub fn find(&self, mut predicate: impl FnMut(NodeId, &Node) -> bool) -> Vec<NodeId> {
+        self.walk(ROOT)
+            .into_iter()
+            .filter(|&id| predicate(id, &self.nodes[id]))
+            .collect()
+    }
+
+    /// The total size of every node, indexed by `NodeId`: a file's own size, or everything
+    /// under a directory. Children come after their parents, so one backwards pass adds each
+    /// node into its parent after the node's own total is complete.
+    pub fn du(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = self
+            .nodes
+            .iter()
+            .map(|node| match node.kind {
+                Kind::File { size } => size,
+                Kind::Dir { .. } => 0,
+            })
+            .collect();
+        for id in (1..self.nodes.len()).rev() {
+            sizes[self.parent(id)] += sizes[id];
+        }
+        sizes
+    }
+
+    /// How many directories down from the root each node is, indexed by `NodeId`.
+    pub fn depths(&self) -> Vec<usize> {
+        let mut depths = vec![0; self.nodes.len()];
+        for id in 1..self.nodes.len() {
+            depths[id] = depths[self.parent(id)] + 1;
+        }
+        depths
+    }
+
+    /// The node furthest from the root, the first in walk order if there's a tie.
+    pub fn deepest(&self) -> NodeId {
+        let depths = self.depths();
+        self.walk(ROOT)
+            .into_iter()
+            .rev()
+            .max_by_key(|&id| depths[id])
+            .unwrap()
+    }
+
+    /// The biggest file, the first in walk order if there's a tie.
+    pub fn largest_file(&self) -> Option<NodeId> {
+        self.walk(ROOT)
+            .into_iter()
+            .rev()
+            .filter_map(|id| match self.nodes[id].kind {
+                Kind::File { size } => Some((size, id)),
+                Kind::Dir { .. } => None,
+            })
+            .max_by_key(|&(size, _)| size)
+            .map(|(_, id)| id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The tree the sample transcript describes.
+    fn sample() -> FileSystem {
+        let mut fs = FileSystem::new();
+        let a = fs.add_dir(ROOT, "a");
+        fs.add_file(ROOT, "b.txt", 14848514);
+        fs.add_file(ROOT, "c.dat", 8504156);
+        let d = fs.add_dir(ROOT, "d");
+        let e = fs.add_dir(a, "e");
+        fs.add_file(a, "f", 29116);
+        fs.add_file(a, "g", 2557);
+        fs.add_file(a, "h.lst", 62596);
+        fs.add_file(e, "i", 584);
+        for (name, size) in [
+            ("j", 4060174),
+            ("d.log", 8033020),
+            ("d.ext", 5626152),
+            ("k", 7214296),
+        ] {
+            fs.add_file(d, name, size);
+        }
+        fs
+    }
+
+    #[test]
+    fn test_paths() {
+        let fs = sample();
+        assert_eq!("/", fs.path(ROOT));
+        let i = fs.lookup("/a/e/i").unwrap();
+        assert_eq!("/a/e/i", fs.path(i));
+        assert_eq!(fs.lookup("/a/e"), Some(fs.parent(i)));
+        assert_eq!(Some(ROOT), fs.lookup("/"));
+        assert_eq!(None, fs.lookup("/a/x"));
+        assert_eq!(None, fs.lookup("/b.txt/x"));
+        assert_eq!(ROOT, fs.parent(ROOT));
+    }
+
+    #[test]
+    fn test_add_existing() {
+        let mut fs = sample();
+        let len = fs.len();
+        assert_eq!(fs.lookup("/a"), Some(fs.add_dir(ROOT, "a")));
+        let d = fs.lookup("/d").unwrap();
+        assert_eq!(fs.lookup("/d/k"), Some(fs.add_file(d, "k", 1)));
+        assert_eq!(len, fs.len());
+    }
+
+    #[test]
+    #[should_panic(expected = "/b.txt is not a directory")]
+    fn test_add_to_file() {
+        let mut fs = sample();
+        let file = fs.lookup("/b.txt").unwrap();
+        fs.add_file(file, "x", 1);
+    }
+
+    #[test]
+    fn test_walk_order() {
+        let fs = sample();
+        let paths: Vec<_> = fs
+            .walk(fs.lookup("/a").unwrap())
+            .into_iter()
+            .map(|id| fs.path(id))
+            .collect();
+        assert_eq!(
+            vec!["/a", "/a/e", "/a/e/i", "/a/f", "/a/g", "/a/h.lst"],
+            paths
+        );
+    }
+
+    #[test]
+    fn test_du() {
+        let fs = sample();
+        let du = fs.du();
+        assert_eq!(584, du[fs.lookup("/a/e").unwrap()]);
+        assert_eq!(94853, du[fs.lookup("/a").unwrap()]);
+        assert_eq!(24933642, du[fs.lookup("/d").unwrap()]);
+        assert_eq!(48381165, du[ROOT]);
+    }
+
+    #[test]
+    fn test_queries() {
+        let fs = sample();
+        assert_eq!("/a/e/i", fs.path(fs.deepest()));
+        assert_eq!("/b.txt", fs.path(fs.largest_file().unwrap()));
+        assert_eq!(None, FileSystem::new().largest_file());
+        assert_eq!(ROOT, FileSystem::new().deepest());
+
+        let logs: Vec<_> = fs
+            .find(|_, node| node.name.starts_with("d."))
+            .into_iter()
+            .map(|id| fs.path(id))
+            .collect();
+        assert_eq!(vec!["/d/d.ext", "/d/d.log"], logs);
+        let du = fs.du();
+        let small = fs.find(|id, node| !node.is_dir() && du[id] < 10_000);
+        assert_eq!(
+            vec![fs.lookup("/a/e/i").unwrap(), fs.lookup("/a/g").unwrap()],
+            small
+        );
+    }
+}
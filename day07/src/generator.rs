@@ -0,0 +1,185 @@
+//! Random file systems, and random transcripts that explore them, for testing the parser.
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::filesystem::{FileSystem, Kind, NodeId, ROOT};
+
+fn random_name(rng: &mut impl Rng) -> String {
+    let len = rng.gen_range(1..=3);
+    let mut name: String = (0..len).map(|_| rng.gen_range('a'..='e')).collect();
+    if rng.gen_bool(0.3) {
+        name += ".";
+        name.push(rng.gen_range('a'..='e'));
+    }
+    name
+}
+
+/// A file system of up to `nodes` nodes below the root, each added to a random directory.
+/// Most files are small and a few are huge, so that both parts of the puzzle have something
+/// to find.
+pub fn random_file_system(rng: &mut impl Rng, nodes: usize) -> FileSystem {
+    let mut fs = FileSystem::new();
+    let mut dirs = vec![ROOT];
+    for _ in 0..nodes {
+        let parent = *dirs.choose(rng).unwrap();
+        let name = random_name(rng);
+        if fs.child(parent, &name).is_some() {
+            continue;
+        }
+        if rng.gen_bool(0.3) {
+            dirs.push(fs.add_dir(parent, &name));
+        } else if rng.gen_bool(0.9) {
+            fs.add_file(parent, &name, rng.gen_range(1..=50_000));
+        } else {
+            fs.add_file(parent, &name, rng.gen_range(1..=20_000_000));
+        }
+    }
+    fs
+}
+
+/// The directories from the root down to `id`, including both.
+fn ancestry(fs: &FileSystem, id: NodeId) -> Vec<NodeId> {
+    let mut ancestry = vec![id];
+    while let Some(parent) = fs.node(*ancestry.last().unwrap()).parent {
+        ancestry.push(parent);
+    }
+    ancestry.reverse();
+    ancestry
+}
+
+/// The `cd`s that get from `from` to `to`: either back to the root and down, or up to where
+/// their paths meet and down from there, whichever `rng` picks.
+fn cd(fs: &FileSystem, rng: &mut impl Rng, from: NodeId, to: NodeId, transcript: &mut Vec<String>) {
+    let from = ancestry(fs, from);
+    let to = ancestry(fs, to);
+    let mut common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    if rng.gen_bool(0.2) {
+        transcript.push("$ cd /".to_owned());
+        common = 1;
+    } else {
+        transcript.extend((common..from.len()).map(|_| "$ cd ..".to_owned()));
+    }
+    transcript.extend(
+        to[common..]
+            .iter()
+            .map(|&id| format!("$ cd {}", fs.node(id).name)),
+    );
+}
+
+/// A valid transcript that lists every directory of `fs`, visiting them in a random order,
+/// listing their contents in a random order, and now and then listing one again.
+pub fn transcript(fs: &FileSystem, rng: &mut impl Rng) -> Vec<String> {
+    let mut transcript = vec!["$ cd /".to_owned()];
+    let mut cwd = ROOT;
+    // Directories that have been seen in a listing, so can be reached, but not listed yet.
+    let mut reachable = vec![ROOT];
+    let mut listed = Vec::new();
+    while !reachable.is_empty() {
+        let dir = if !listed.is_empty() && rng.gen_bool(0.1) {
+            *listed.choose(rng).unwrap()
+        } else {
+            let dir = reachable.swap_remove(rng.gen_range(0..reachable.len()));
+            listed.push(dir);
+            dir
+        };
+        cd(fs, rng, cwd, dir, &mut transcript);
+        cwd = dir;
+
+        transcript.push("$ ls".to_owned());
+        let mut children: Vec<_> = fs.children(dir).collect();
+        children.shuffle(rng);
+        for child in children {
+            let node = fs.node(child);
+            match node.kind {
+                Kind::Dir { .. } => {
+                    transcript.push(format!("dir {}", node.name));
+                    if !listed.contains(&child) && !reachable.contains(&child) {
+                        reachable.push(child);
+                    }
+                }
+                Kind::File { size } => transcript.push(format!("{} {}", size, node.name)),
+            }
+        }
+    }
+    transcript
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2, transcript::parse};
+    use helpers::read_lines_panicky;
+    use proptest::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    /// Every path in walk order, with its size if it's a file. Unlike `FileSystem`'s own
+    /// equality, this doesn't depend on the order the nodes were added in.
+    fn shape(fs: &FileSystem) -> Vec<(String, Option<usize>)> {
+        fs.walk(ROOT)
+            .into_iter()
+            .map(|id| match fs.node(id).kind {
+                Kind::Dir { .. } => (fs.path(id), None),
+                Kind::File { size } => (fs.path(id), Some(size)),
+            })
+            .collect()
+    }
+
+    /// Each directory's size, found by recursing through the tree rather than with `du`.
+    fn dir_sizes(fs: &FileSystem, id: NodeId, sizes: &mut Vec<usize>) -> usize {
+        let size = match fs.node(id).kind {
+            Kind::File { size } => return size,
+            Kind::Dir { .. } => fs.children(id).map(|c| dir_sizes(fs, c, sizes)).sum(),
+        };
+        sizes.push(size);
+        size
+    }
+
+    #[test]
+    fn test_sample_round_trips() {
+        let lines: Vec<String> = read_lines_panicky("test_input.txt").collect();
+        let (sample, _) = parse(&lines);
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..100 {
+            let transcript = transcript(&sample, &mut rng);
+            let (fs, inconsistencies) = parse(&transcript);
+            assert!(inconsistencies.is_empty(), "{:?}", inconsistencies);
+            assert_eq!(shape(&sample), shape(&fs));
+            assert_eq!(95437, part1(&fs));
+            assert_eq!(24933642, part2(&fs));
+        }
+    }
+
+    #[test]
+    fn test_empty_file_system() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let transcript = transcript(&FileSystem::new(), &mut rng);
+        assert_eq!(vec!["$ cd /", "$ ls"], transcript);
+    }
+
+    proptest! {
+        #[test]
+        fn parsing_recovers_the_tree(seed in any::<u64>(), nodes in 0..200usize) {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let generated = random_file_system(&mut rng, nodes);
+            let (parsed, inconsistencies) = parse(&transcript(&generated, &mut rng));
+            prop_assert!(inconsistencies.is_empty(), "{:?}", inconsistencies);
+            prop_assert_eq!(shape(&generated), shape(&parsed));
+        }
+
+        #[test]
+        fn answers_match_direct_computation(seed in any::<u64>(), nodes in 1..200usize) {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let generated = random_file_system(&mut rng, nodes);
+            let (parsed, _) = parse(&transcript(&generated, &mut rng));
+
+            let mut sizes = Vec::new();
+            let total = dir_sizes(&generated, ROOT, &mut sizes);
+            let small: usize = sizes.iter().filter(|&&s| s < 100_000).sum();
+            prop_assert_eq!(small, part1(&parsed));
+            if total > 40_000_000 {
+                let smallest = sizes.iter().filter(|&&s| s > total - 40_000_000).min();
+                prop_assert_eq!(*smallest.unwrap(), part2(&parsed));
+            }
+        }
+    }
+}
@@ -1,117 +1,132 @@
-use std::{collections::HashMap, path::{PathBuf, Path}};
+use filesystem::{FileSystem, NodeId, ROOT};
+use helpers::{read_lines_panicky, Args, Results};
+use transcript::Inconsistency;
 
-use helpers::read_lines_panicky;
+mod filesystem;
+#[cfg(test)]
+mod generator;
+mod report;
+mod transcript;
 
-fn main() {
-    let tree = parse_file_system("input.txt");
-    println!("Part 1: {}", part1(&tree));
-    println!("Part 2: {}", part2(&tree));
-}
+const DISK_SIZE: usize = 70_000_000;
+const NEEDED_SPACE: usize = 30_000_000;
 
-fn parse_file_system(path: &str) -> HashMap<PathBuf, Dir> {
-    let lines: Vec<String> = read_lines_panicky(path).skip(1).collect(); // skip cd into /
-    let root = Dir::default();
-    let mut path = PathBuf::from("/"); 
-    let mut tree: HashMap<PathBuf, Dir> = HashMap::new();
-    tree.insert(path.clone(), root);
-
-    let mut i = 0;
-    while i < lines.len() {
-        let line = &lines[i];
-        i += 1;
-        let command = Command::parse(&line);
-        match command {
-            Command::CdIn(dir_name) => {
-                // thanks AxlLind for showing me this API
-                path.push(dir_name);
-            }
-            Command::CdOut => {
-                let _ = path.pop();
-            }
-            Command::Ls => {
-                let mut current_dir = tree.remove(&path).unwrap();
-                for line in (&lines[i..]).iter().take_while(|l| !l.starts_with("$")) {
-                    i += 1;
-                    if line.starts_with("dir") {
-                        let name = line.split_at(4).1;
-                        current_dir.child_dirs.push(name.to_owned());
-                        let new_path = path.join(name);
-                        tree.insert(new_path, Dir::default());
-                    } else {
-                        let (size, _) = line.split_once(" ").unwrap();
-                        current_dir.size_of_files += size.parse::<usize>().unwrap();
-                    }
-                }
-                tree.insert(path.clone(), current_dir);
-            }
+fn main() {
+    let args = Args::parse_with(
+        env!("CARGO_MANIFEST_DIR"),
+        &[
+            "find=", "du", "du=", "human", "tree", "depth=", "json", "stats", "validate",
+        ],
+    );
+    let lines: Vec<String> = read_lines_panicky(&args.input).collect();
+    let (fs, inconsistencies) = transcript::parse(&lines);
+    check_transcript(&args, &inconsistencies);
+
+    if let Some(name) = args.option("find") {
+        let du = fs.du();
+        for id in fs.find(|_, node| node.name == name) {
+            println!("{}\t{}", du[id], fs.path(id));
         }
+        return;
+    }
+    let human = args.flag("human");
+    if let Some(path) = args.option("du") {
+        let Some(id) = fs.lookup(path) else {
+            eprintln!("error: no such file or directory: {}", path);
+            std::process::exit(1);
+        };
+        println!(
+            "{}\t{}",
+            report::format_size(fs.du()[id], human),
+            fs.path(id)
+        );
+        return;
+    }
+    if args.flag("du") {
+        print!("{}", report::du_table(&fs, human));
+        return;
+    }
+    if args.flag("tree") {
+        print!("{}", report::tree(&fs, args.parsed_option("depth"), human));
+        return;
+    }
+    if args.flag("json") {
+        println!("{}", report::to_json(&fs));
+        return;
+    }
+    if args.flag("stats") {
+        print_stats(&fs);
+        return;
     }
 
-    tree
-}
-
-fn part1(tree: &HashMap<PathBuf, Dir>) -> usize {
-    let mut sizes = Vec::new();
-    let root = PathBuf::from("/");
-    get_all_sizes(tree, &root, &mut sizes);
-
-    sizes.into_iter().filter(|&s| s < 100_000).sum()
-}
-
-fn part2(tree: &HashMap<PathBuf, Dir>) -> usize {
-    let mut sizes = Vec::new();
-    let root = PathBuf::from("/");
-    let total_size = get_all_sizes(tree, &root, &mut sizes);
-    let minimum_to_delete = total_size - 40_000_000;
-
-    sizes.sort();
-    sizes.into_iter().find(|&s| s > minimum_to_delete).unwrap()
+    let mut results = Results::new(7, &args);
+    results.part(1, || part1(&fs));
+    results.part(2, || part2(&fs));
+    results.print();
 }
 
-fn get_all_sizes(tree: &HashMap<PathBuf, Dir>, path: &Path, sizes: &mut Vec<usize>) -> usize {
-    let dir = tree.get(path).unwrap();
-    let mut size = dir.size_of_files;
-    for child in &dir.child_dirs {
-        let path = path.join(child);
-        size += get_all_sizes(tree, &path, sizes);
+// Contradictions mean the tree, and so both answers, could be wrong, so they're reported up
+// front. `--validate` reports them on stdout and stops there.
+fn check_transcript(args: &Args, inconsistencies: &[Inconsistency]) {
+    if args.flag("validate") {
+        for inconsistency in inconsistencies {
+            println!("{}", inconsistency);
+        }
+        println!("{} inconsistencies", inconsistencies.len());
+        std::process::exit(if inconsistencies.is_empty() { 0 } else { 1 });
     }
 
-    sizes.push(size);
-
-    size
+    if !inconsistencies.is_empty() {
+        for inconsistency in inconsistencies {
+            eprintln!("error: {}", inconsistency);
+        }
+        eprintln!(
+            "error: {} inconsistencies in the transcript",
+            inconsistencies.len()
+        );
+        std::process::exit(1);
+    }
 }
 
-enum Command<'a> {
-    CdIn(&'a str),
-    CdOut,
-    Ls,
+fn part1(fs: &FileSystem) -> usize {
+    let du = fs.du();
+    fs.dirs().map(|dir| du[dir]).filter(|&s| s < 100_000).sum()
 }
 
-impl<'a> Command<'a> {
-    fn parse(input: &'a str) -> Command<'a> {
-        if input.starts_with("$ cd ") {
-            if input.starts_with("$ cd ..") {
-                Command::CdOut
-            } else {
-                let dir = input.split_at(5).1;
-                Command::CdIn(dir)
-            }
-        } else {
-            Command::Ls
-        }
-    }
+fn part2(fs: &FileSystem) -> usize {
+    let du = fs.du();
+    let minimum_to_delete = du[ROOT] - (DISK_SIZE - NEEDED_SPACE);
+    let smallest: Option<NodeId> = fs
+        .dirs()
+        .filter(|&dir| du[dir] > minimum_to_delete)
+        .min_by_key(|&dir| du[dir]);
+    du[smallest.unwrap()]
 }
 
-#[derive(Default)]
-struct Dir {
-    size_of_files: usize,
-    child_dirs: Vec<String>,
+fn print_stats(fs: &FileSystem) {
+    let du = fs.du();
+    let dirs = fs.dirs().count();
+    println!(
+        "{} directories, {} files, {} bytes",
+        dirs,
+        fs.len() - dirs,
+        du[ROOT]
+    );
+    println!("deepest: {}", fs.path(fs.deepest()));
+    if let Some(file) = fs.largest_file() {
+        println!("largest file: {} ({} bytes)", fs.path(file), du[file]);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn parse_file_system(path: &str) -> FileSystem {
+        let lines: Vec<String> = read_lines_panicky(path).collect();
+        transcript::parse(&lines).0
+    }
+
     #[test]
     fn part1_sample() {
         let tree = parse_file_system("test_input.txt");
@@ -0,0 +1,219 @@
+use serde::Serialize;
+
+use crate::filesystem::{FileSystem, Kind, NodeId, ROOT};
+
+/// A size the way `du -h` prints it: bytes below 1K, otherwise the smallest unit that keeps
+/// it under 1024, rounded up, with one decimal below 10.
+pub fn human_size(bytes: usize) -> String {
+    const UNITS: [&str; 5] = ["K", "M", "G", "T", "P"];
+    if bytes < 1024 {
+        return bytes.to_string();
+    }
+    let mut unit = 0;
+    let mut scale = 1024;
+    while bytes.div_ceil(scale) >= 1024 && unit < UNITS.len() - 1 {
+        scale *= 1024;
+        unit += 1;
+    }
+    // In u128 so that sizes near `usize::MAX` don't overflow.
+    let tenths = (bytes as u128 * 10).div_ceil(scale as u128);
+    if tenths < 100 {
+        format!("{}.{}{}", tenths / 10, tenths % 10, UNITS[unit])
+    } else {
+        format!("{}{}", bytes.div_ceil(scale), UNITS[unit])
+    }
+}
+
+/// A size in bytes, or as `human_size` if `human` is set.
+pub fn format_size(bytes: usize, human: bool) -> String {
+    if human {
+        human_size(bytes)
+    } else {
+        bytes.to_string()
+    }
+}
+
+/// The file system drawn like `tree --du -s`: every node with its total size, siblings in
+/// name order, and nothing more than `max_depth` levels below the root.
+pub fn tree(fs: &FileSystem, max_depth: Option<usize>, human: bool) -> String {
+    let du = fs.du();
+    let depths = fs.depths();
+    let shown = |id: NodeId| max_depth.is_none_or(|max| depths[id] <= max);
+    let width = fs
+        .walk(ROOT)
+        .into_iter()
+        .filter(|&id| shown(id))
+        .map(|id| format_size(du[id], human).len())
+        .max()
+        .unwrap();
+
+    let mut tree = format!("[{:>width$}]  /\n", format_size(du[ROOT], human));
+    // Each entry is a node still to draw and the prefix that lines up its branch.
+    let mut stack: Vec<(NodeId, String)> = Vec::new();
+    let push_children = |stack: &mut Vec<_>, id, prefix: &str| {
+        let children: Vec<_> = fs.children(id).filter(|&child| shown(child)).collect();
+        for (i, &child) in children.iter().enumerate().rev() {
+            let last = i == children.len() - 1;
+            stack.push((
+                child,
+                format!("{}{}", prefix, if last { "└── " } else { "├── " }),
+            ));
+        }
+    };
+    push_children(&mut stack, ROOT, "");
+    while let Some((id, branch)) = stack.pop() {
+        let node = fs.node(id);
+        tree += &format!(
+            "{}[{:>width$}]  {}\n",
+            branch,
+            format_size(du[id], human),
+            node.name
+        );
+        // Below a node, its own branch becomes a plain line down to its next sibling, if any.
+        let prefix = branch.replace("├── ", "│   ").replace("└── ", "    ");
+        push_children(&mut stack, id, &prefix);
+    }
+    tree
+}
+
+/// Every directory's total size, largest first, like `du | sort -rn`.
+pub fn du_table(fs: &FileSystem, human: bool) -> String {
+    let du = fs.du();
+    let mut dirs: Vec<(usize, String)> = fs.dirs().map(|dir| (du[dir], fs.path(dir))).collect();
+    dirs.sort_unstable_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    dirs.into_iter()
+        .map(|(bytes, path)| format!("{}\t{}\n", format_size(bytes, human), path))
+        .collect()
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum JsonNode<'a> {
+    Dir {
+        name: &'a str,
+        size: usize,
+        children: Vec<JsonNode<'a>>,
+    },
+    File {
+        name: &'a str,
+        size: usize,
+    },
+}
+
+fn json_node<'a>(fs: &'a FileSystem, du: &[usize], id: NodeId) -> JsonNode<'a> {
+    let node = fs.node(id);
+    let name = if id == ROOT { "/" } else { &node.name };
+    match node.kind {
+        Kind::Dir { .. } => JsonNode::Dir {
+            name,
+            size: du[id],
+            children: fs
+                .children(id)
+                .map(|child| json_node(fs, du, child))
+                .collect(),
+        },
+        Kind::File { size } => JsonNode::File { name, size },
+    }
+}
+
+/// The whole tree as nested JSON objects, each with a `type` of `dir` or `file`, a `name` and
+/// a `size`, which for directories is their total. Directories also have their `children`.
+pub fn to_json(fs: &FileSystem) -> String {
+    serde_json::to_string_pretty(&json_node(fs, &fs.du(), ROOT)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript;
+    use helpers::read_lines_panicky;
+    use serde_json::Value;
+
+    fn sample() -> FileSystem {
+        let lines: Vec<String> = read_lines_panicky("test_input.txt").collect();
+        transcript::parse(&lines).0
+    }
+
+    #[test]
+    fn test_human_size() {
+        let cases = [
+            (0, "0"),
+            (1023, "1023"),
+            (1024, "1.0K"),
+            (1025, "1.1K"),
+            (10 * 1024, "10K"),
+            (94853, "93K"),
+            (1024 * 1024 - 1, "1.0M"),
+            (24933642, "24M"),
+            (48381165, "47M"),
+            (5 << 40, "5.0T"),
+            (usize::MAX, "16384P"),
+        ];
+        for (bytes, expected) in cases {
+            assert_eq!(expected, human_size(bytes), "{} bytes", bytes);
+        }
+    }
+
+    #[test]
+    fn test_tree() {
+        let expected = "\
+[48381165]  /
+├── [   94853]  a
+│   ├── [     584]  e
+│   │   └── [     584]  i
+│   ├── [   29116]  f
+│   ├── [    2557]  g
+│   └── [   62596]  h.lst
+├── [14848514]  b.txt
+├── [ 8504156]  c.dat
+└── [24933642]  d
+    ├── [ 5626152]  d.ext
+    ├── [ 8033020]  d.log
+    ├── [ 4060174]  j
+    └── [ 7214296]  k
+";
+        assert_eq!(expected, tree(&sample(), None, false));
+    }
+
+    #[test]
+    fn test_tree_depth_and_human_sizes() {
+        let expected = "\
+[ 47M]  /
+├── [ 93K]  a
+├── [ 15M]  b.txt
+├── [8.2M]  c.dat
+└── [ 24M]  d
+";
+        assert_eq!(expected, tree(&sample(), Some(1), true));
+        assert_eq!("[0]  /\n", tree(&FileSystem::new(), Some(0), false));
+    }
+
+    #[test]
+    fn test_du_table() {
+        assert_eq!(
+            "48381165\t/\n24933642\t/d\n94853\t/a\n584\t/a/e\n",
+            du_table(&sample(), false)
+        );
+        assert_eq!(
+            "47M\t/\n24M\t/d\n93K\t/a\n584\t/a/e\n",
+            du_table(&sample(), true)
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let json: Value = serde_json::from_str(&to_json(&sample())).unwrap();
+        assert_eq!("dir", json["type"]);
+        assert_eq!("/", json["name"]);
+        assert_eq!(48381165, json["size"]);
+        let e = &json["children"][0]["children"][0];
+        assert_eq!(
+            ("e", 584),
+            (e["name"].as_str().unwrap(), e["size"].as_u64().unwrap())
+        );
+        let i = &e["children"][0];
+        assert_eq!("file", i["type"]);
+        assert_eq!(584, i["size"]);
+        assert!(i.get("children").is_none());
+    }
+}
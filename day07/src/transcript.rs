@@ -0,0 +1,356 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+};
+
+use crate::filesystem::{FileSystem, Kind, NodeId, ROOT};
+
+/// Something in the transcript that contradicts what came before it, or can't be made sense
+/// of. Line numbers are 1-based. The file system keeps whatever it was first told.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inconsistency {
+    Malformed {
+        line: usize,
+        text: String,
+    },
+    /// A name listed as a directory in one listing and a file in another.
+    KindChanged {
+        line: usize,
+        path: String,
+        was_dir: bool,
+    },
+    SizeChanged {
+        line: usize,
+        path: String,
+        was: usize,
+        now: usize,
+    },
+    /// Something in an earlier listing of a directory that a later one left out.
+    Missing {
+        line: usize,
+        path: String,
+    },
+    /// A `cd` into a directory that no listing has mentioned. It's created anyway.
+    Unlisted {
+        line: usize,
+        path: String,
+    },
+    CdIntoFile {
+        line: usize,
+        path: String,
+    },
+}
+
+fn kind(is_dir: bool) -> &'static str {
+    if is_dir {
+        "a directory"
+    } else {
+        "a file"
+    }
+}
+
+impl Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Inconsistency::Malformed { line, text } => {
+                write!(f, "line {}: can't make sense of `{}`", line, text)
+            }
+            Inconsistency::KindChanged {
+                line,
+                path,
+                was_dir,
+            } => write!(
+                f,
+                "line {}: {} was listed as {}, now as {}",
+                line,
+                path,
+                kind(*was_dir),
+                kind(!was_dir)
+            ),
+            Inconsistency::SizeChanged {
+                line,
+                path,
+                was,
+                now,
+            } => write!(
+                f,
+                "line {}: {} was listed with size {}, now {}",
+                line, path, was, now
+            ),
+            Inconsistency::Missing { line, path } => write!(
+                f,
+                "line {}: {} is missing from this listing of its directory",
+                line, path
+            ),
+            Inconsistency::Unlisted { line, path } => {
+                write!(
+                    f,
+                    "line {}: cd into {}, which hasn't been listed",
+                    line, path
+                )
+            }
+            Inconsistency::CdIntoFile { line, path } => {
+                write!(f, "line {}: can't cd into {}, it's a file", line, path)
+            }
+        }
+    }
+}
+
+enum Command<'a> {
+    CdRoot,
+    CdIn(&'a str),
+    CdOut,
+    Ls,
+}
+
+impl<'a> Command<'a> {
+    fn parse(input: &'a str) -> Option<Command<'a>> {
+        match input.strip_prefix("$ ")? {
+            "ls" => Some(Command::Ls),
+            "cd /" => Some(Command::CdRoot),
+            "cd .." => Some(Command::CdOut),
+            command => command.strip_prefix("cd ").map(Command::CdIn),
+        }
+    }
+}
+
+/// The `ls` whose output is being read.
+struct Listing {
+    dir: NodeId,
+    line: usize,
+    names: HashSet<String>,
+    /// Whether `dir` has been listed before, so anything missing from this listing is a
+    /// contradiction.
+    repeat: bool,
+}
+
+struct Parser {
+    fs: FileSystem,
+    cwd: NodeId,
+    listing: Option<Listing>,
+    listed: HashSet<NodeId>,
+    inconsistencies: Vec<Inconsistency>,
+}
+
+/// Rebuilds the file system a transcript explores. Listing a directory again only adds what
+/// wasn't there before, and anything that contradicts an earlier listing is reported rather
+/// than applied.
+pub fn parse<S: AsRef<str>>(lines: &[S]) -> (FileSystem, Vec<Inconsistency>) {
+    let mut parser = Parser {
+        fs: FileSystem::new(),
+        cwd: ROOT,
+        listing: None,
+        listed: HashSet::new(),
+        inconsistencies: Vec::new(),
+    };
+    for (i, line) in lines.iter().enumerate() {
+        parser.line(i + 1, line.as_ref());
+    }
+    parser.end_listing();
+    (parser.fs, parser.inconsistencies)
+}
+
+impl Parser {
+    fn line(&mut self, line: usize, text: &str) {
+        if !text.starts_with('$') {
+            self.entry(line, text);
+            return;
+        }
+
+        self.end_listing();
+        match Command::parse(text) {
+            Some(Command::CdRoot) => self.cwd = ROOT,
+            Some(Command::CdOut) => self.cwd = self.fs.parent(self.cwd),
+            Some(Command::CdIn(name)) => self.cd(line, name),
+            Some(Command::Ls) => {
+                self.listing = Some(Listing {
+                    dir: self.cwd,
+                    line,
+                    names: HashSet::new(),
+                    repeat: !self.listed.insert(self.cwd),
+                })
+            }
+            None => self.malformed(line, text),
+        }
+    }
+
+    fn cd(&mut self, line: usize, name: &str) {
+        match self.fs.child(self.cwd, name) {
+            Some(dir) if self.fs.node(dir).is_dir() => self.cwd = dir,
+            Some(file) => self.inconsistencies.push(Inconsistency::CdIntoFile {
+                line,
+                path: self.fs.path(file),
+            }),
+            None => {
+                self.cwd = self.fs.add_dir(self.cwd, name);
+                self.inconsistencies.push(Inconsistency::Unlisted {
+                    line,
+                    path: self.fs.path(self.cwd),
+                });
+            }
+        }
+    }
+
+    fn entry(&mut self, line: usize, text: &str) {
+        let Some(listing) = &mut self.listing else {
+            return self.malformed(line, text);
+        };
+        let Some((size, name)) = text.split_once(' ') else {
+            return self.malformed(line, text);
+        };
+        let size = match size {
+            "dir" => None,
+            size => match size.parse() {
+                Ok(size) => Some(size),
+                Err(_) => return self.malformed(line, text),
+            },
+        };
+        listing.names.insert(name.to_owned());
+        let dir = listing.dir;
+
+        let Some(existing) = self.fs.child(dir, name) else {
+            match size {
+                None => self.fs.add_dir(dir, name),
+                Some(size) => self.fs.add_file(dir, name, size),
+            };
+            return;
+        };
+        let path = self.fs.path(existing);
+        let inconsistency = match (&self.fs.node(existing).kind, size) {
+            (Kind::Dir { .. }, None) => return,
+            (Kind::File { size: was }, Some(now)) if *was == now => return,
+            (&Kind::File { size: was }, Some(now)) => Inconsistency::SizeChanged {
+                line,
+                path,
+                was,
+                now,
+            },
+            (kind, _) => Inconsistency::KindChanged {
+                line,
+                path,
+                was_dir: matches!(kind, Kind::Dir { .. }),
+            },
+        };
+        self.inconsistencies.push(inconsistency);
+    }
+
+    fn end_listing(&mut self) {
+        let Some(listing) = self.listing.take() else {
+            return;
+        };
+        if !listing.repeat {
+            return;
+        }
+        for child in self.fs.children(listing.dir) {
+            if !listing.names.contains(&self.fs.node(child).name) {
+                self.inconsistencies.push(Inconsistency::Missing {
+                    line: listing.line,
+                    path: self.fs.path(child),
+                });
+            }
+        }
+    }
+
+    fn malformed(&mut self, line: usize, text: &str) {
+        self.inconsistencies.push(Inconsistency::Malformed {
+            line,
+            text: text.to_owned(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::read_lines_panicky;
+
+    fn messages(lines: &[&str]) -> Vec<String> {
+        parse(lines).1.iter().map(|i| i.to_string()).collect()
+    }
+
+    #[test]
+    fn test_inputs_are_consistent() {
+        for path in ["test_input.txt", "input.txt"] {
+            let lines: Vec<String> = read_lines_panicky(path).collect();
+            assert_eq!(Vec::<Inconsistency>::new(), parse(&lines).1);
+        }
+    }
+
+    #[test]
+    fn test_cd_root_mid_session() {
+        let mut lines: Vec<String> = read_lines_panicky("test_input.txt").collect();
+        let (fs, _) = parse(&lines);
+        // Going back to the root and listing things again changes nothing.
+        lines.extend(
+            [
+                "$ cd /",
+                "$ ls",
+                "dir a",
+                "14848514 b.txt",
+                "8504156 c.dat",
+                "dir d",
+            ]
+            .map(String::from),
+        );
+        lines.extend(["$ cd a", "$ cd e", "$ ls", "584 i"].map(String::from));
+        assert_eq!((fs, vec![]), parse(&lines));
+    }
+
+    #[test]
+    fn test_repeated_listing_is_idempotent() {
+        let lines = ["$ ls", "10 f", "dir d", "$ ls", "dir d", "10 f", "20 g"];
+        let (fs, inconsistencies) = parse(&lines);
+        assert!(inconsistencies.is_empty());
+        assert_eq!(30, fs.du()[ROOT]);
+    }
+
+    #[test]
+    fn test_contradictions() {
+        let lines = [
+            "$ cd /", "$ ls", "10 f", "dir d", "20 g", "$ ls", "11 f", "5 d", "$ cd d", "$ ls",
+            "dir f",
+        ];
+        assert_eq!(
+            vec![
+                "line 7: /f was listed with size 10, now 11",
+                "line 8: /d was listed as a directory, now as a file",
+                "line 6: /g is missing from this listing of its directory",
+            ],
+            messages(&lines)
+        );
+        // The first listing wins.
+        let (fs, _) = parse(&lines);
+        assert_eq!(30, fs.du()[ROOT]);
+        assert!(fs.lookup("/d/f").is_some_and(|f| fs.node(f).is_dir()));
+    }
+
+    #[test]
+    fn test_bad_cds() {
+        let lines = [
+            "$ ls", "1 f", "$ cd f", "$ cd x", "$ ls", "2 g", "$ cd ..", "$ cd ..",
+        ];
+        assert_eq!(
+            vec![
+                "line 3: can't cd into /f, it's a file",
+                "line 4: cd into /x, which hasn't been listed",
+            ],
+            messages(&lines)
+        );
+        let (fs, _) = parse(&lines);
+        assert!(fs.lookup("/x/g").is_some());
+    }
+
+    #[test]
+    fn test_malformed() {
+        let lines = ["1 f", "$ pwd", "$ ls", "dir", "x f"];
+        assert_eq!(
+            vec![
+                "line 1: can't make sense of `1 f`",
+                "line 2: can't make sense of `$ pwd`",
+                "line 4: can't make sense of `dir`",
+                "line 5: can't make sense of `x f`",
+            ],
+            messages(&lines)
+        );
+    }
+}
@@ -1,22 +1,27 @@
-use std::{collections::HashSet, time::Instant};
+use std::collections::HashSet;
 
-use helpers::read_lines_panicky;
+use helpers::{read_lines_panicky, Args, Results};
+
+mod scenic;
 
 fn main() {
-    let forest = Forest::parse("input.txt");
-    let now = Instant::now();
-    println!("Part 1: {} ({:?})", part1(&forest), now.elapsed());
-    let now = Instant::now();
-    println!("Part 2: {} ({:?})", part2(&forest), now.elapsed());
+    let args = Args::parse(env!("CARGO_MANIFEST_DIR"));
+    let forest = Forest::parse(&args.input);
+    let mut results = Results::new(8, &args);
+    results.part(1, || part1(&forest));
+    results.part(2, || part2(&forest));
+    results.print();
 }
 
 fn part1(forest: &Forest) -> usize {
     let mut visible = HashSet::new();
-    for i in 0..forest.grid_size {
-        let horizontal = forest.horizontal(i);
-        let vertical = forest.vertical(i);
+    for y in 0..forest.height {
+        let horizontal = forest.horizontal(y);
         visible.extend(visible_from_outside(horizontal.iter()).map(|t| t.to_xy()));
         visible.extend(visible_from_outside(horizontal.iter().rev()).map(|t| t.to_xy()));
+    }
+    for x in 0..forest.width {
+        let vertical = forest.vertical(x);
         visible.extend(visible_from_outside(vertical.iter()).map(|t| t.to_xy()));
         visible.extend(visible_from_outside(vertical.iter().rev()).map(|t| t.to_xy()));
     }
@@ -25,41 +30,7 @@ fn part1(forest: &Forest) -> usize {
 }
 
 fn part2(forest: &Forest) -> usize {
-    let mut max_score = 0;
-    let verticals: Vec<_> = (0..forest.grid_size).map(|j| forest.vertical(j)).collect();
-
-    for i in 0..forest.grid_size {
-        let horizontal = forest.horizontal(i);
-        for j in 0..forest.grid_size {
-            let vertical = &verticals[j];
-            let &Tree { x, y, height } = &horizontal[j];
-
-            let (left, right) = horizontal.split_at(x);
-            let right = &right[1..];
-            let (up, down) = vertical.split_at(y);
-            let down = &down[1..];
-
-            let score = score_view(height, left.iter().rev())
-                * score_view(height, right.iter())
-                * score_view(height, up.iter().rev())
-                * score_view(height, down.iter());
-
-            max_score = max_score.max(score);
-        }
-    }
-
-    max_score
-}
-
-fn score_view<'a>(base_height: u8, view: impl Iterator<Item = &'a Tree>) -> usize {
-    let mut score = 0;
-    for tree in view {
-        score += 1;
-        if tree.height >= base_height {
-            break;
-        }
-    }
-    score
+    scenic::scenic_scores(forest).into_iter().max().unwrap_or(0)
 }
 
 fn visible_from_outside<'a>(
@@ -93,47 +64,68 @@ impl Tree {
     }
 }
 
+/// The tree heights in row-major order, `width` trees to a row.
 #[derive(PartialEq, Eq, Debug)]
 struct Forest {
-    pub grid_size: usize,
+    width: usize,
+    height: usize,
     trees: Vec<u8>,
 }
 
 impl Forest {
     fn parse(path: &str) -> Self {
-        let lines: Vec<_> = read_lines_panicky(path).collect();
-        let grid_size = lines.len();
+        Self::from_lines(read_lines_panicky(path))
+    }
+
+    fn from_lines<S: AsRef<str>>(lines: impl IntoIterator<Item = S>) -> Self {
         let mut trees = Vec::new();
+        let mut width = None;
+        let mut height = 0;
         for line in lines {
+            let line = line.as_ref();
+            let row_width = *width.get_or_insert(line.len());
+            assert_eq!(
+                row_width,
+                line.len(),
+                "Row {} is a different width from the first",
+                height + 1
+            );
             trees.extend(line.chars().map(|c| c.to_digit(10).unwrap() as u8));
+            height += 1;
+        }
+        Self {
+            width: width.unwrap_or(0),
+            height,
+            trees,
         }
-        Self { grid_size, trees }
     }
 
-    fn horizontal(&self, idx: usize) -> Vec<Tree> {
-        let start = idx * self.grid_size;
-        let mut trees = Vec::new();
-        for i in 0..self.grid_size {
-            let x = i;
-            let y = idx;
-            let cur = start + i;
-            let height = self.trees[cur];
-            trees.push(Tree::new(x, y, height));
-        }
-        trees
+    fn row(&self, y: usize) -> &[u8] {
+        &self.trees[y * self.width..(y + 1) * self.width]
     }
 
-    fn vertical(&self, idx: usize) -> Vec<Tree> {
-        let start = idx;
-        let mut vec = Vec::new();
-        for i in 0..self.grid_size {
-            let x = idx;
-            let y = i;
-            let cur = start + (i * self.grid_size);
-            let height = self.trees[cur];
-            vec.push(Tree::new(x, y, height))
-        }
-        vec
+    fn column(&self, x: usize) -> Vec<u8> {
+        self.trees[x..]
+            .iter()
+            .step_by(self.width)
+            .copied()
+            .collect()
+    }
+
+    fn horizontal(&self, y: usize) -> Vec<Tree> {
+        self.row(y)
+            .iter()
+            .enumerate()
+            .map(|(x, &height)| Tree::new(x, y, height))
+            .collect()
+    }
+
+    fn vertical(&self, x: usize) -> Vec<Tree> {
+        self.column(x)
+            .into_iter()
+            .enumerate()
+            .map(|(y, height)| Tree::new(x, y, height))
+            .collect()
     }
 }
 
@@ -149,7 +141,8 @@ mod tests {
     #[test]
     fn parse_forest() {
         let expected = Forest {
-            grid_size: 5,
+            width: 5,
+            height: 5,
             trees: vec![
                 3, 0, 3, 7, 3, 2, 5, 5, 1, 2, 6, 5, 3, 3, 2, 3, 3, 5, 4, 9, 3, 5, 3, 9, 0,
             ],
@@ -232,7 +225,7 @@ mod tests {
         assert_eq!(
             vec![Tree::new(0, 4, 3), Tree::new(1, 4, 5), Tree::new(3, 4, 9)],
             visible_from_outside(
-                vec![
+                [
                     Tree::new(0, 4, 3),
                     Tree::new(1, 4, 5),
                     Tree::new(2, 4, 3),
@@ -246,6 +239,35 @@ mod tests {
         )
     }
 
+    #[test]
+    fn rectangular_forests() {
+        let wide = Forest::from_lines(["30373", "25512", "65332"]);
+        assert_eq!((5, 3), (wide.width, wide.height));
+        assert_eq!(vec![7, 1, 3], wide.column(3));
+        assert_eq!(14, part1(&wide));
+        assert_eq!(2, part2(&wide));
+
+        let tall = Forest::from_lines(["326", "055", "353", "713", "323"]);
+        assert_eq!(14, part1(&tall));
+        assert_eq!(2, part2(&tall));
+
+        let single = Forest::from_lines(["5"]);
+        assert_eq!((1, 0), (part1(&single), part2(&single)));
+
+        for empty in [
+            Forest::from_lines(Vec::<&str>::new()),
+            Forest::from_lines([""]),
+        ] {
+            assert_eq!((0, 0), (part1(&empty), part2(&empty)));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Row 2 is a different width from the first")]
+    fn ragged_forest() {
+        Forest::from_lines(["123", "45"]);
+    }
+
     #[test]
     fn part1_final() {
         assert_eq!(1803, part1(&Forest::parse("input.txt")));
@@ -0,0 +1,159 @@
+use crate::Forest;
+
+/// How far each tree in a line can see looking back towards the start of the line: up to and
+/// including the first tree at least as tall, or to the edge.
+///
+/// The stack holds the trees that could still block a later tree's view, shortest on top. A
+/// tree hides everything shorter than itself from the trees after it, so it pops them before
+/// being pushed, and each tree is pushed and popped at most once: O(n) for the line.
+pub fn viewing_distances(heights: &[u8]) -> Vec<usize> {
+    let mut stack: Vec<usize> = Vec::new();
+    let mut distances = Vec::with_capacity(heights.len());
+    for (i, &height) in heights.iter().enumerate() {
+        while stack.last().is_some_and(|&j| heights[j] < height) {
+            stack.pop();
+        }
+        distances.push(match stack.last() {
+            Some(&blocker) => i - blocker,
+            None => i,
+        });
+        stack.push(i);
+    }
+    distances
+}
+
+/// The distances looking both ways along a line, multiplied into `scores` at the given
+/// indices.
+fn multiply_line(scores: &mut [usize], indices: impl Iterator<Item = usize>, heights: &[u8]) {
+    let towards_start = viewing_distances(heights);
+    let reversed: Vec<u8> = heights.iter().rev().copied().collect();
+    let towards_end = viewing_distances(&reversed);
+    for (i, index) in indices.enumerate() {
+        scores[index] *= towards_start[i] * towards_end[heights.len() - 1 - i];
+    }
+}
+
+/// Every tree's scenic score, in row-major order. Each row and column is scanned once in each
+/// direction, so the whole forest takes time proportional to its number of trees.
+pub fn scenic_scores(forest: &Forest) -> Vec<usize> {
+    let mut scores = vec![1; forest.width * forest.height];
+    for y in 0..forest.height {
+        let row = forest.row(y);
+        multiply_line(
+            &mut scores,
+            (0..forest.width).map(|x| y * forest.width + x),
+            row,
+        );
+    }
+    for x in 0..forest.width {
+        let column = forest.column(x);
+        multiply_line(
+            &mut scores,
+            (0..forest.height).map(|y| y * forest.width + x),
+            &column,
+        );
+    }
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tree;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::time::Instant;
+
+    fn random_forest(rng: &mut impl Rng, width: usize, height: usize) -> Forest {
+        let tallest = rng.gen_range(0..=9);
+        let lines: Vec<String> = (0..height)
+            .map(|_| {
+                (0..width)
+                    .map(|_| char::from(b'0' + rng.gen_range(0..=tallest)))
+                    .collect()
+            })
+            .collect();
+        Forest::from_lines(lines)
+    }
+
+    /// Every tree's score, walking outwards from each tree in turn: the original part 2.
+    fn walking_scores(forest: &Forest) -> Vec<usize> {
+        let mut scores = Vec::new();
+        let verticals: Vec<_> = (0..forest.width).map(|x| forest.vertical(x)).collect();
+
+        for y in 0..forest.height {
+            let horizontal = forest.horizontal(y);
+            for vertical in &verticals {
+                let &Tree { x, y, height } = &vertical[y];
+
+                let (left, right) = horizontal.split_at(x);
+                let right = &right[1..];
+                let (up, down) = vertical.split_at(y);
+                let down = &down[1..];
+
+                scores.push(
+                    score_view(height, left.iter().rev())
+                        * score_view(height, right.iter())
+                        * score_view(height, up.iter().rev())
+                        * score_view(height, down.iter()),
+                );
+            }
+        }
+        scores
+    }
+
+    fn score_view<'a>(base_height: u8, view: impl Iterator<Item = &'a Tree>) -> usize {
+        let mut score = 0;
+        for tree in view {
+            score += 1;
+            if tree.height >= base_height {
+                break;
+            }
+        }
+        score
+    }
+
+    #[test]
+    fn test_viewing_distances() {
+        assert_eq!(vec![0, 1, 2, 3, 1], viewing_distances(&[3, 0, 3, 7, 3]));
+        assert_eq!(vec![0, 1, 1, 1, 2], viewing_distances(&[2, 5, 5, 1, 2]));
+        assert_eq!(vec![0, 1, 2, 3], viewing_distances(&[9, 1, 2, 3]));
+        assert!(viewing_distances(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_matches_walking() {
+        let mut rng = StdRng::seed_from_u64(8);
+        for _ in 0..200 {
+            let (width, height) = (rng.gen_range(1..20), rng.gen_range(1..20));
+            let forest = random_forest(&mut rng, width, height);
+            assert_eq!(walking_scores(&forest), scenic_scores(&forest));
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_scenic_scores() {
+        let mut rng = StdRng::seed_from_u64(8);
+        for size in [99, 500, 2000] {
+            let forest = random_forest(&mut rng, size, size);
+
+            let start = Instant::now();
+            let walking = walking_scores(&forest);
+            let walking_time = start.elapsed();
+
+            let start = Instant::now();
+            let stack = scenic_scores(&forest);
+            let stack_time = start.elapsed();
+
+            assert_eq!(walking, stack);
+            println!(
+                "{}x{}: walking {:?}, monotonic stack {:?} ({:.1}x)",
+                size,
+                size,
+                walking_time,
+                stack_time,
+                walking_time.as_secs_f64() / stack_time.as_secs_f64()
+            );
+        }
+    }
+}
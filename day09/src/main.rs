@@ -1,10 +1,13 @@
 use std::collections::HashSet;
 
-use helpers::read_lines_panicky;
+use helpers::{read_lines_panicky, Args, Results};
 
 fn main() {
-    println!("Part 1: {}", part1("input.txt"));
-    println!("Part 2: {}", part2("input.txt"));
+    let args = Args::parse(env!("CARGO_MANIFEST_DIR"));
+    let mut results = Results::new(9, &args);
+    results.part(1, || part1(&args.input));
+    results.part(2, || part2(&args.input));
+    results.print();
 }
 
 fn part1(path: &str) -> usize {
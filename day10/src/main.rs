@@ -1,14 +1,12 @@
-use std::time::Instant;
-
-use helpers::read_lines_panicky;
+use helpers::{read_lines_panicky, Args, Results};
 
 fn main() {
-    let instructions = instructions("input.txt");
-    let now = Instant::now();
-    let cpu = run(&instructions);
-    println!("Elapsed: {:?}", now.elapsed());
-    println!("Part 1: {}", cpu.signal_strength);
-    println!("Part 2:\n{}", cpu.print());
+    let args = Args::parse(env!("CARGO_MANIFEST_DIR"));
+    let instructions = instructions(&args.input);
+    let mut results = Results::new(10, &args);
+    results.part(1, || run(&instructions).signal_strength);
+    results.part(2, || run(&instructions).print());
+    results.print();
 }
 
 fn instructions(path: &str) -> Vec<String> {
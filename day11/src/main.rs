@@ -1,14 +1,14 @@
-use std::{collections::VecDeque, fmt::Debug, time::Instant};
+use std::{collections::VecDeque, fmt::Debug};
+
+use helpers::{read_string_panicky, Args, Results};
 
 fn main() {
-    let input = std::fs::read_to_string("input.txt").unwrap();
-    let monkeys = parse_monkeys(&input);
-    let now = Instant::now();
-    println!("Part 1: {} ({:?})", part1(monkeys), now.elapsed());
-
-    let monkeys = parse_monkeys(&input);
-    let now = Instant::now();
-    println!("Part 2: {} ({:?})", part2(monkeys), now.elapsed());
+    let args = Args::parse(env!("CARGO_MANIFEST_DIR"));
+    let input = read_string_panicky(&args.input);
+    let mut results = Results::new(11, &args);
+    results.part(1, || part1(parse_monkeys(&input)));
+    results.part(2, || part2(parse_monkeys(&input)));
+    results.print();
 }
 
 fn part1(mut monkeys: Vec<Monkey>) -> usize {
@@ -35,6 +35,8 @@ fn product_of_top_two(monkeys: Vec<Monkey>) -> usize {
     counts.iter().rev().take(2).product()
 }
 
+// `is_multiple_of` would need Rust 1.87.
+#[allow(clippy::manual_is_multiple_of)]
 fn simulate_round<R>(monkeys: &mut [Monkey], reduce_worry: R) 
 where
     R: Fn(usize) -> usize,
@@ -93,7 +95,11 @@ struct Monkey {
 // }
 
 fn parse_monkeys(input: &str) -> Vec<Monkey> {
-    input.split("\r\n\r\n").map(parse_monkey).collect()
+    input
+        .replace("\r\n", "\n")
+        .split("\n\n")
+        .map(parse_monkey)
+        .collect()
 }
 
 fn parse_monkey(input: &str) -> Monkey {
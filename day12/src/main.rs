@@ -1,13 +1,14 @@
-use std::{time::Instant, collections::{VecDeque, HashMap}};
+use std::collections::{HashMap, VecDeque};
 
-use helpers::read_lines_panicky;
+use helpers::{read_lines_panicky, Args, Results};
 
 fn main() {
-    let grid = parse_grid("input.txt");
-    let now = Instant::now();
-    println!("Part 1: {} ({:?})", part1(&grid), now.elapsed());
-    let now = Instant::now();
-    println!("Part 2: {} ({:?})", part2(&grid), now.elapsed());
+    let args = Args::parse(env!("CARGO_MANIFEST_DIR"));
+    let grid = parse_grid(&args.input);
+    let mut results = Results::new(12, &args);
+    results.part(1, || part1(&grid));
+    results.part(2, || part2(&grid));
+    results.print();
 }
 
 fn parse_grid(path: &str) -> Vec<Vec<u8>> {
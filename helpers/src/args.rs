@@ -0,0 +1,281 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::ErrorKind,
+    path::Path,
+    str::FromStr,
+};
+
+use crate::STDIN;
+
+fn usage(accepted: &[&str]) -> String {
+    let mut usage = "[--format text|json|csv]".to_owned();
+    for option in accepted {
+        match option.strip_suffix('=') {
+            Some(name) => usage += &format!(" [--{}=VALUE]", name),
+            None => usage += &format!(" [--{}]", option),
+        }
+    }
+    usage + " [input path, or - for stdin]"
+}
+
+/// How a day prints its answers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl Format {
+    fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown format `{}`", s)),
+        }
+    }
+}
+
+/// Command line arguments shared by every day: `dayNN [--format ...] [input path | -]`, plus
+/// whichever options the day accepts for itself. Those are declared by name: `"name"` for a
+/// flag, `--name`, and `"name="` for an option taking a value, `--name=value` or
+/// `--name value`. A name can be declared both ways. Anything else is an error.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Args {
+    pub input: String,
+    pub format: Format,
+    options: BTreeMap<String, Option<String>>,
+}
+
+impl Args {
+    /// Parses the process arguments, exiting with a usage message on bad input. Without an
+    /// explicit path the day's `input.txt` is used, found via the crate's manifest directory
+    /// (pass `env!("CARGO_MANIFEST_DIR")`) so it doesn't depend on the working directory.
+    pub fn parse(crate_dir: &str) -> Self {
+        Self::parse_with(crate_dir, &[])
+    }
+
+    /// Like `parse`, for a day that accepts options of its own.
+    pub fn parse_with(crate_dir: &str, accepted: &[&str]) -> Self {
+        let mut args = std::env::args();
+        let program = args.next().unwrap_or_default();
+        Self::from_args(crate_dir, accepted, args).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            eprintln!("usage: {} {}", program, usage(accepted));
+            std::process::exit(2);
+        })
+    }
+
+    pub fn from_args(
+        crate_dir: &str,
+        accepted: &[&str],
+        mut args: impl Iterator<Item = String>,
+    ) -> Result<Self, String> {
+        let is_flag = |name: &str| accepted.contains(&name);
+        let takes_value = |name: &str| accepted.iter().any(|a| a.strip_suffix('=') == Some(name));
+        let mut input = None;
+        let mut format = Format::default();
+        let mut options = BTreeMap::new();
+
+        while let Some(arg) = args.next() {
+            if arg == "--format" {
+                let value = args.next().ok_or("missing value for `--format`")?;
+                format = Format::parse(&value)?;
+            } else if let Some(value) = arg.strip_prefix("--format=") {
+                format = Format::parse(value)?;
+            } else if let Some(option) = arg.strip_prefix("--") {
+                let (name, value) = match option.split_once('=') {
+                    Some((name, value)) if takes_value(name) => (name, Some(value.to_owned())),
+                    Some((name, _)) if is_flag(name) => {
+                        return Err(format!("`--{}` doesn't take a value", name))
+                    }
+                    None if is_flag(option) => (option, None),
+                    None if takes_value(option) => {
+                        let value = args
+                            .next()
+                            .ok_or_else(|| format!("missing value for `--{}`", option))?;
+                        (option, Some(value))
+                    }
+                    _ => return Err(format!("unknown option `{}`", arg)),
+                };
+                options.insert(name.to_owned(), value);
+            } else if input.is_some() {
+                return Err(format!("unexpected argument `{}`", arg));
+            } else {
+                input = Some(arg);
+            }
+        }
+
+        let input = input.unwrap_or_else(|| {
+            Path::new(crate_dir)
+                .join("input.txt")
+                .to_string_lossy()
+                .into_owned()
+        });
+        if input != STDIN {
+            check_input(&input)?;
+        }
+
+        Ok(Self {
+            input,
+            format,
+            options,
+        })
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name)?.as_deref()
+    }
+
+    /// Parses the value of `--name=value`, exiting with an error if it is malformed.
+    pub fn parsed_option<T: FromStr>(&self, name: &str) -> Option<T> {
+        let value = self.option(name)?;
+        match value.parse() {
+            Ok(parsed) => Some(parsed),
+            Err(_) => {
+                eprintln!("error: invalid value `{}` for `--{}`", value, name);
+                std::process::exit(2);
+            }
+        }
+    }
+}
+
+/// Checks that `path` can be read as input. Anything that isn't a directory is accepted,
+/// including pipes like `<(...)`. Only regular files are opened, as opening a pipe would block
+/// or lose what's written to it.
+fn check_input(path: &str) -> Result<(), String> {
+    let metadata = fs::metadata(path).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => format!("input file `{}` not found", path),
+        _ => format!("can't read input file `{}`: {}", path, e),
+    })?;
+    if metadata.is_dir() {
+        return Err(format!("input `{}` is a directory, not a file", path));
+    }
+    if metadata.is_file() {
+        File::open(path).map_err(|e| format!("can't read input file `{}`: {}", path, e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn default_input_is_relative_to_crate() {
+        let crate_dir = env!("CARGO_MANIFEST_DIR");
+        let parsed = Args::from_args(crate_dir, &[], args(&["Cargo.toml"])).unwrap();
+        assert_eq!("Cargo.toml", parsed.input);
+
+        let err = Args::from_args(crate_dir, &[], args(&[])).unwrap_err();
+        assert!(err.contains(&format!("{}/input.txt", crate_dir)), "{}", err);
+    }
+
+    #[test]
+    fn stdin_input() {
+        let parsed = Args::from_args("", &[], args(&["-"])).unwrap();
+        assert_eq!(STDIN, parsed.input);
+        assert_eq!(Format::Text, parsed.format);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn special_file_input() {
+        // Like `<(cat input.txt)`, which is a pipe rather than a regular file.
+        let parsed = Args::from_args("", &[], args(&["/dev/null"])).unwrap();
+        assert_eq!("/dev/null", parsed.input);
+    }
+
+    #[test]
+    fn format_option() {
+        let parsed = Args::from_args("", &[], args(&["--format", "json", "-"])).unwrap();
+        assert_eq!(Format::Json, parsed.format);
+        let parsed = Args::from_args("", &[], args(&["-", "--format=csv"])).unwrap();
+        assert_eq!(Format::Csv, parsed.format);
+        assert_eq!(
+            Err("unknown format `xml`".to_owned()),
+            Args::from_args("", &[], args(&["--format", "xml"]))
+        );
+    }
+
+    const DAY_OPTIONS: &[&str] = &["report", "top=", "du", "du="];
+
+    #[test]
+    fn day_options() {
+        let parsed = Args::from_args("", DAY_OPTIONS, args(&["--report", "-", "--top=5"])).unwrap();
+        assert_eq!(STDIN, parsed.input);
+        assert!(parsed.flag("report"));
+        assert!(!parsed.flag("top5"));
+        assert_eq!(Some("5"), parsed.option("top"));
+        assert_eq!(Some(5), parsed.parsed_option::<usize>("top"));
+        assert_eq!(None, parsed.option("report"));
+    }
+
+    #[test]
+    fn option_values() {
+        let parsed = Args::from_args("", DAY_OPTIONS, args(&["--top", "5", "-"])).unwrap();
+        assert_eq!(STDIN, parsed.input);
+        assert_eq!(Some("5"), parsed.option("top"));
+
+        // Declared both ways, a bare option is a flag rather than taking the next argument.
+        let parsed = Args::from_args("", DAY_OPTIONS, args(&["--du", "-"])).unwrap();
+        assert!(parsed.flag("du") && parsed.option("du").is_none());
+        assert_eq!(STDIN, parsed.input);
+        let parsed = Args::from_args("", DAY_OPTIONS, args(&["--du=/a", "-"])).unwrap();
+        assert_eq!(Some("/a"), parsed.option("du"));
+    }
+
+    #[test]
+    fn bad_options() {
+        let error = |a: &[&str]| Args::from_args("", DAY_OPTIONS, args(a)).unwrap_err();
+        assert_eq!("unknown option `--skip-bda`", error(&["--skip-bda", "-"]));
+        assert_eq!(
+            "unknown option `--report`",
+            Args::from_args("", &[], args(&["--report"])).unwrap_err()
+        );
+        assert_eq!(
+            "`--report` doesn't take a value",
+            error(&["--report=yes", "-"])
+        );
+        assert_eq!("missing value for `--top`", error(&["--top"]));
+    }
+
+    #[test]
+    fn usage_lists_options() {
+        assert_eq!(
+            "[--format text|json|csv] [--report] [--top=VALUE] [--du] [--du=VALUE] \
+             [input path, or - for stdin]",
+            usage(DAY_OPTIONS)
+        );
+    }
+
+    #[test]
+    fn missing_input() {
+        assert_eq!(
+            Err("input file `nope.txt` not found".to_owned()),
+            Args::from_args("", &[], args(&["nope.txt"]))
+        );
+        assert_eq!(
+            Err("input `src` is a directory, not a file".to_owned()),
+            Args::from_args("", &[], args(&["src"]))
+        );
+        assert_eq!(
+            Err("unexpected argument `extra`".to_owned()),
+            Args::from_args("", &[], args(&["-", "extra"]))
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+};
+
+use crate::{results::InputHasher, STDIN};
+
+/// Puzzle input read as it's needed, straight from the file or stdin, rather than all at once.
+/// Everything read is hashed on the way past, so a day that streams its input can still report
+/// `input_hash` once it has read to the end.
+pub struct Input {
+    reader: Box<dyn BufRead>,
+    hasher: InputHasher,
+}
+
+impl Input {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let reader: Box<dyn BufRead> = if path.as_ref() == Path::new(STDIN) {
+            Box::new(io::stdin().lock())
+        } else {
+            Box::new(BufReader::new(File::open(path)?))
+        };
+        Ok(Self {
+            reader,
+            hasher: InputHasher::default(),
+        })
+    }
+
+    pub fn open_panicky<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref();
+        Self::open(path).unwrap_or_else(|e| panic!("Failed to open {}: {}", path.display(), e))
+    }
+
+    pub fn lines_panicky(&mut self) -> impl Iterator<Item = String> + '_ {
+        self.lines().map(|r| r.expect("Failed to read line"))
+    }
+
+    /// The hash of everything read so far, which is the whole input's `input_hash` once the
+    /// input has been read to the end.
+    pub fn hash(&self) -> String {
+        self.hasher.finish()
+    }
+}
+
+impl Read for Input {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+impl BufRead for Input {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The bytes being consumed are still buffered, so this doesn't read anything new.
+        if let Ok(buf) = self.reader.fill_buf() {
+            self.hasher.update(&buf[..amt]);
+        }
+        self.reader.consume(amt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{input_hash, read_string_panicky};
+
+    #[test]
+    fn lines_and_hash_match_reading_whole() {
+        let mut input = Input::open_panicky("Cargo.toml");
+        let lines: Vec<_> = input.lines_panicky().collect();
+        let whole = read_string_panicky("Cargo.toml");
+        assert_eq!(whole.lines().collect::<Vec<_>>(), lines);
+        assert_eq!(input_hash(whole.as_bytes()), input.hash());
+    }
+}
@@ -0,0 +1,334 @@
+use std::{fmt, str::FromStr};
+
+/// An inclusive range of integers `start..=end`, never empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    start: i64,
+    end: i64,
+}
+
+impl Interval {
+    pub fn new(start: i64, end: i64) -> Self {
+        assert!(start <= end, "Interval {}-{} is backwards", start, end);
+        Self { start, end }
+    }
+
+    pub fn point(at: i64) -> Self {
+        Self::new(at, at)
+    }
+
+    pub fn start(&self) -> i64 {
+        self.start
+    }
+
+    pub fn end(&self) -> i64 {
+        self.end
+    }
+
+    /// How many integers the interval covers. There's no `is_empty`, since it's never empty.
+    /// The full range of `i64` covers 2^64 integers, one more than fits in a `u64`.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u128 {
+        self.end.abs_diff(self.start) as u128 + 1
+    }
+
+    pub fn contains(&self, point: i64) -> bool {
+        self.start <= point && point <= self.end
+    }
+
+    /// Whether every point of `other` is also in `self`.
+    pub fn contains_interval(&self, other: Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Whether the two intervals overlap or sit right next to each other.
+    pub fn touches(&self, other: Interval) -> bool {
+        self.start <= other.end.saturating_add(1) && other.start <= self.end.saturating_add(1)
+    }
+
+    pub fn intersection(&self, other: Interval) -> Option<Interval> {
+        self.overlaps(other)
+            .then(|| Interval::new(self.start.max(other.start), self.end.min(other.end)))
+    }
+
+    /// The single interval covering both, if they touch; otherwise there's a gap between them
+    /// and the union needs an `IntervalSet`.
+    pub fn union(&self, other: Interval) -> Option<Interval> {
+        self.touches(other)
+            .then(|| Interval::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+
+    /// The points of `self` that aren't in `other`: none, one or two intervals.
+    pub fn difference(&self, other: Interval) -> Vec<Interval> {
+        if !self.overlaps(other) {
+            return vec![*self];
+        }
+        let mut pieces = Vec::with_capacity(2);
+        if self.start < other.start {
+            pieces.push(Interval::new(self.start, other.start - 1));
+        }
+        if other.end < self.end {
+            pieces.push(Interval::new(other.end + 1, self.end));
+        }
+        pieces
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Parses `start-end`, as in day04's section assignments. Either end may be negative.
+impl FromStr for Interval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid interval `{}`", s);
+        // Skip the first character so a leading minus sign isn't taken as the separator.
+        let split = s
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| c == '-')
+            .ok_or_else(invalid)?
+            .0;
+        let start = s[..split].trim().parse().map_err(|_| invalid())?;
+        let end = s[split + 1..].trim().parse().map_err(|_| invalid())?;
+        if start > end {
+            return Err(format!("interval `{}` is backwards", s));
+        }
+        Ok(Interval::new(start, end))
+    }
+}
+
+/// A set of integers stored as sorted, disjoint intervals. Intervals that overlap or touch
+/// are merged as they're added, so the representation is always minimal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// How many integers are in the set.
+    pub fn len(&self) -> u128 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn contains(&self, point: i64) -> bool {
+        let i = self.intervals.partition_point(|i| i.end < point);
+        self.intervals.get(i).is_some_and(|i| i.contains(point))
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        // Everything from `first` up to `last` touches the new interval and merges into it.
+        let first = self
+            .intervals
+            .partition_point(|i| i.end.saturating_add(1) < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|i| i.start <= interval.end.saturating_add(1));
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, &i| merged.union(i).unwrap());
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn remove(&mut self, interval: Interval) {
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+        let pieces: Vec<_> = self.intervals[first..last]
+            .iter()
+            .flat_map(|i| i.difference(interval))
+            .collect();
+        self.intervals.splice(first..last, pieces);
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut union = self.clone();
+        for &interval in &other.intervals {
+            union.insert(interval);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = Vec::new();
+        let (mut a, mut b) = (0, 0);
+        while let (Some(x), Some(y)) = (self.intervals.get(a), other.intervals.get(b)) {
+            intervals.extend(x.intersection(*y));
+            if x.end < y.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut difference = self.clone();
+        for &interval in &other.intervals {
+            difference.remove(interval);
+        }
+        difference
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = Interval>>(iter: T) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, interval) in self.intervals.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", interval)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iv(start: i64, end: i64) -> Interval {
+        Interval::new(start, end)
+    }
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet {
+        intervals.iter().map(|&(s, e)| iv(s, e)).collect()
+    }
+
+    #[test]
+    fn interval_basics() {
+        let a = iv(2, 8);
+        assert_eq!(7, a.len());
+        assert_eq!(1, Interval::point(-3).len());
+        assert!(a.contains(2) && a.contains(8) && !a.contains(9));
+        assert!(a.contains_interval(iv(3, 7)) && a.contains_interval(a));
+        assert!(!a.contains_interval(iv(3, 9)));
+        assert!(a.overlaps(iv(8, 10)) && !a.overlaps(iv(9, 10)));
+        assert!(a.touches(iv(9, 10)) && !a.touches(iv(10, 11)));
+    }
+
+    #[test]
+    fn interval_arithmetic() {
+        let a = iv(2, 8);
+        assert_eq!(Some(iv(6, 8)), a.intersection(iv(6, 99)));
+        assert_eq!(None, a.intersection(iv(9, 99)));
+        assert_eq!(Some(iv(2, 10)), a.union(iv(9, 10)));
+        assert_eq!(None, a.union(iv(10, 11)));
+        assert_eq!(vec![iv(2, 3), iv(7, 8)], a.difference(iv(4, 6)));
+        assert_eq!(vec![iv(2, 3)], a.difference(iv(4, 20)));
+        assert_eq!(vec![a], a.difference(iv(9, 20)));
+        assert!(a.difference(iv(0, 20)).is_empty());
+    }
+
+    #[test]
+    fn interval_extremes() {
+        let all = iv(i64::MIN, i64::MAX);
+        assert_eq!(1 << 63, iv(i64::MIN, -1).len());
+        assert_eq!(1 << 64, all.len());
+        assert_eq!(1 << 64, IntervalSet::from_iter([all]).len());
+        let halves = set(&[(i64::MIN, -1), (1, i64::MAX)]);
+        assert_eq!((1 << 64) - 1, halves.len());
+        assert!(all.touches(iv(0, 0)));
+        assert_eq!(vec![iv(i64::MIN, -1), iv(1, i64::MAX)], all.difference(iv(0, 0)));
+    }
+
+    #[test]
+    fn interval_parse_and_display() {
+        assert_eq!(Ok(iv(2, 4)), "2-4".parse());
+        assert_eq!(Ok(iv(-5, -3)), "-5--3".parse());
+        assert_eq!(Ok(iv(-5, 3)), "-5-3".parse());
+        assert_eq!(Err("invalid interval `2`".to_owned()), "2".parse::<Interval>());
+        assert_eq!(Err("invalid interval `a-4`".to_owned()), "a-4".parse::<Interval>());
+        assert_eq!(
+            Err("interval `4-2` is backwards".to_owned()),
+            "4-2".parse::<Interval>()
+        );
+        assert_eq!("-5--3", iv(-5, -3).to_string());
+    }
+
+    #[test]
+    fn set_merges_on_insert() {
+        let s = set(&[(10, 12), (1, 3), (5, 6), (4, 4), (20, 25), (11, 21)]);
+        assert_eq!(&[iv(1, 6), iv(10, 25)], s.intervals());
+        assert_eq!(22, s.len());
+        assert_eq!("1-6,10-25", s.to_string());
+        assert!(s.contains(4) && s.contains(25) && !s.contains(8) && !s.contains(26));
+        assert!(IntervalSet::new().is_empty() && !IntervalSet::new().contains(0));
+    }
+
+    #[test]
+    fn set_remove() {
+        let mut s = set(&[(1, 10), (20, 30)]);
+        s.remove(iv(5, 22));
+        assert_eq!(&[iv(1, 4), iv(23, 30)], s.intervals());
+        s.remove(iv(0, 100));
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(1, 5), (10, 15), (20, 25)]);
+        let b = set(&[(4, 11), (14, 22)]);
+        assert_eq!(set(&[(1, 25)]), a.union(&b));
+        assert_eq!(set(&[(4, 5), (10, 11), (14, 15), (20, 22)]), a.intersection(&b));
+        assert_eq!(set(&[(1, 3), (12, 13), (23, 25)]), a.difference(&b));
+        assert_eq!(set(&[(6, 9), (16, 19)]), b.difference(&a));
+    }
+
+    #[test]
+    fn set_matches_brute_force() {
+        // Pseudo-random intervals in a small range, checked point by point.
+        let mut seed = 12345u64;
+        let mut next = |n: u64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((seed >> 33) % n) as i64
+        };
+        for _ in 0..200 {
+            let mut intervals = Vec::new();
+            for _ in 0..next(6) {
+                let start = next(40);
+                intervals.push(iv(start, start + next(8)));
+            }
+            let a: IntervalSet = intervals.iter().copied().collect();
+            let b = set(&[(next(20), 20 + next(20))]);
+            for point in -1..50 {
+                let in_a = intervals.iter().any(|i| i.contains(point));
+                let in_b = b.contains(point);
+                assert_eq!(in_a, a.contains(point));
+                assert_eq!(in_a || in_b, a.union(&b).contains(point));
+                assert_eq!(in_a && in_b, a.intersection(&b).contains(point));
+                assert_eq!(in_a && !in_b, a.difference(&b).contains(point));
+            }
+        }
+    }
+}
@@ -1,15 +1,82 @@
-use std::fs::File;
-use std::io::{self, BufRead, BufReader};
-use std::path::Path;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Lines, Read};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 
-pub fn read_lines<P: AsRef<Path>>(path: P) -> io::Result<impl Iterator<Item = io::Result<String>>> {
-    let file = File::open(path.as_ref())?;
-    let reader = BufReader::new(file);
+mod args;
+mod input;
+mod interval;
+mod results;
+
+pub use args::{Args, Format};
+pub use input::Input;
+pub use interval::{Interval, IntervalSet};
+pub use results::{input_hash, InputHasher, PartResult, Results};
+
+/// Input path meaning "read the puzzle input from stdin".
+pub const STDIN: &str = "-";
+
+pub fn read_lines<P: AsRef<Path>>(path: P) -> io::Result<Lines<Box<dyn BufRead>>> {
+    let reader: Box<dyn BufRead> = if readable_once(path.as_ref()) {
+        Box::new(read_once(path.as_ref())?.as_bytes())
+    } else {
+        Box::new(BufReader::new(File::open(path.as_ref())?))
+    };
     Ok(reader.lines())
 }
 
 pub fn read_lines_panicky<P: AsRef<Path>>(path: P) -> impl Iterator<Item = String> {
+    let path = path.as_ref();
     read_lines(path)
-        .expect("Failed to open file")
+        .unwrap_or_else(|e| panic!("Failed to open {}: {}", path.display(), e))
         .map(|r| r.expect("Failed to read line"))
-}
\ No newline at end of file
+}
+
+pub fn read_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    if readable_once(path.as_ref()) {
+        Ok(read_once(path.as_ref())?.to_owned())
+    } else {
+        std::fs::read_to_string(path)
+    }
+}
+
+pub fn read_string_panicky<P: AsRef<Path>>(path: P) -> String {
+    let path = path.as_ref();
+    read_string(path).unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e))
+}
+
+fn readable_once(path: &Path) -> bool {
+    path == Path::new(STDIN) || fs::metadata(path).is_ok_and(|m| !m.is_file())
+}
+
+// Stdin and pipes like `<(...)` can only be consumed once, but days often read their input more
+// than once, so they're read whole the first time and kept.
+fn read_once(path: &Path) -> io::Result<&'static str> {
+    static CONTENTS: OnceLock<Mutex<HashMap<PathBuf, &'static str>>> = OnceLock::new();
+    let mut contents = CONTENTS.get_or_init(Default::default).lock().unwrap();
+    if let Some(read) = contents.get(path) {
+        return Ok(read);
+    }
+
+    let mut read = String::new();
+    if path == Path::new(STDIN) {
+        io::stdin().read_to_string(&mut read)?;
+    } else {
+        File::open(path)?.read_to_string(&mut read)?;
+    }
+    let read: &'static str = Box::leak(read.into_boxed_str());
+    contents.insert(path.to_owned(), read);
+    Ok(read)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_lines_from_file() {
+        let lines: Vec<_> = read_lines_panicky("Cargo.toml").take(2).collect();
+        assert_eq!(vec!["[package]", "name = \"helpers\""], lines);
+    }
+}
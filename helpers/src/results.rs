@@ -0,0 +1,223 @@
+use std::{fmt::Display, time::Duration, time::Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{read_string_panicky, Args, Format};
+
+/// One solved part, in the schema emitted by `--format json|csv`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub input_hash: String,
+    pub elapsed_ns: u64,
+}
+
+/// Collects timed answers for a day and prints them in the requested format.
+pub struct Results {
+    day: u32,
+    format: Format,
+    input_hash: String,
+    parts: Vec<PartResult>,
+}
+
+impl Results {
+    pub fn new(day: u32, args: &Args) -> Self {
+        let input = read_string_panicky(&args.input);
+        let mut results = Self::streamed(day, args);
+        results.input_hash = input_hash(input.as_bytes());
+        results
+    }
+
+    /// For a day that streams its input through an `Input` instead of reading it whole, so
+    /// the hash is only known once a part has read everything. See `set_input_hash`.
+    pub fn streamed(day: u32, args: &Args) -> Self {
+        Self {
+            day,
+            format: args.format,
+            input_hash: String::new(),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Sets the input hash of every part, including those already recorded.
+    pub fn set_input_hash(&mut self, input_hash: String) {
+        for part in &mut self.parts {
+            part.input_hash = input_hash.clone();
+        }
+        self.input_hash = input_hash;
+    }
+
+    /// Times `solve` and records its answer. Trailing newlines are dropped so multi-line
+    /// answers (like day10's CRT) look the same in every format.
+    pub fn part<T: Display>(&mut self, part: u32, solve: impl FnOnce() -> T) {
+        let now = Instant::now();
+        let answer = solve().to_string();
+        let elapsed = now.elapsed();
+
+        self.parts.push(PartResult {
+            day: self.day,
+            part,
+            answer: answer.trim_end_matches(['\r', '\n']).to_owned(),
+            input_hash: self.input_hash.clone(),
+            elapsed_ns: elapsed.as_nanos() as u64,
+        });
+    }
+
+    pub fn parts(&self) -> &[PartResult] {
+        &self.parts
+    }
+
+    pub fn render(&self) -> String {
+        match self.format {
+            Format::Text => self.render_text(),
+            Format::Json => serde_json::to_string_pretty(&self.parts).unwrap() + "\n",
+            Format::Csv => self.render_csv(),
+        }
+    }
+
+    pub fn print(&self) {
+        print!("{}", self.render());
+    }
+
+    fn render_text(&self) -> String {
+        let mut out = String::new();
+        for result in &self.parts {
+            let elapsed = Duration::from_nanos(result.elapsed_ns);
+            if result.answer.contains('\n') {
+                out += &format!("Part {}: ({:?})\n{}\n", result.part, elapsed, result.answer);
+            } else {
+                out += &format!("Part {}: {} ({:?})\n", result.part, result.answer, elapsed);
+            }
+        }
+        out
+    }
+
+    fn render_csv(&self) -> String {
+        let mut out = String::from("day,part,answer,input_hash,elapsed_ns\n");
+        for r in &self.parts {
+            out += &format!(
+                "{},{},{},{},{}\n",
+                r.day,
+                r.part,
+                csv_field(&r.answer),
+                r.input_hash,
+                r.elapsed_ns
+            );
+        }
+        out
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// 64-bit FNV-1a of the input, as hex. Unlike `DefaultHasher` this is stable across Rust
+/// versions, so dashboards can tell which input an answer belongs to.
+pub fn input_hash(bytes: &[u8]) -> String {
+    let mut hasher = InputHasher::default();
+    hasher.update(bytes);
+    hasher.finish()
+}
+
+/// `input_hash` fed a piece at a time, for input that's streamed rather than read whole.
+#[derive(Debug, Clone, Copy)]
+pub struct InputHasher(u64);
+
+impl Default for InputHasher {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl InputHasher {
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    pub fn finish(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results(format: Format) -> Results {
+        let mut results = Results {
+            day: 10,
+            format,
+            input_hash: input_hash(b"noop\n"),
+            parts: Vec::new(),
+        };
+        results.part(1, || 13140);
+        results.part(2, || "##..\n#.#,\n");
+        for part in results.parts.iter_mut() {
+            part.elapsed_ns = 1500;
+        }
+        results
+    }
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!("cbf29ce484222325", input_hash(b""));
+        assert_eq!("af63dc4c8601ec8c", input_hash(b"a"));
+    }
+
+    #[test]
+    fn test_input_hasher() {
+        let mut hasher = InputHasher::default();
+        hasher.update(b"noop\n");
+        hasher.update(b"addx 3\n");
+        assert_eq!(input_hash(b"noop\naddx 3\n"), hasher.finish());
+    }
+
+    #[test]
+    fn test_set_input_hash() {
+        let mut results = results(Format::Text);
+        results.set_input_hash(input_hash(b"a"));
+        results.part(3, || 0);
+        assert!(results
+            .parts()
+            .iter()
+            .all(|p| p.input_hash == "af63dc4c8601ec8c"));
+    }
+
+    #[test]
+    fn render_text() {
+        assert_eq!(
+            "Part 1: 13140 (1.5µs)\nPart 2: (1.5µs)\n##..\n#.#,\n",
+            results(Format::Text).render()
+        );
+    }
+
+    #[test]
+    fn render_csv() {
+        let hash = input_hash(b"noop\n");
+        assert_eq!(
+            format!(
+                "day,part,answer,input_hash,elapsed_ns\n10,1,13140,{0},1500\n10,2,\"##..\n#.#,\",{0},1500\n",
+                hash
+            ),
+            results(Format::Csv).render()
+        );
+    }
+
+    #[test]
+    fn render_json_round_trips() {
+        let results = results(Format::Json);
+        let parsed: Vec<PartResult> = serde_json::from_str(&results.render()).unwrap();
+        assert_eq!(results.parts(), &parsed[..]);
+        assert_eq!("##..\n#.#,", parsed[1].answer);
+    }
+}